@@ -30,8 +30,8 @@ fn main() -> anyhow::Result<()> {
         panic!("The current sender can not publish to this namespace. Please use the wallet that owns the Account that owns the Namespace.")
     }
 
-    // QUEST #6
     // Publish the App to the Abstract Platform
+    publisher.publish_app::<DCA<Daemon>>()?;
 
     Ok(())
 }
@@ -13,7 +13,8 @@ use croncat_app::contract::{CRONCAT_ID, CRONCAT_MODULE_VERSION};
 use crate::{
     error::DCAError,
     handlers,
-    msg::{AppInstantiateMsg, DCAExecuteMsg, DCAQueryMsg},
+    msg::{AppInstantiateMsg, DCAExecuteMsg, DCAQueryMsg, DCASudoMsg},
+    replies::{self, SWAP_REPLY_ID},
 };
 
 /// The version of your app
@@ -24,22 +25,32 @@ pub const DCA_APP_ID: &str = "abstract:dca";
 /// The type of the result returned by your app's entry points.
 pub type AppResult<T = Response> = Result<T, DCAError>;
 
-/// QUEST #3.2
-///  The custom instantiate message is set to `Empty` but we want to set some state on instantiation.
-/// Replace it with our custom instantiate message type.
 /// The type of the app that is used to build your app and access the Abstract SDK features.
-pub type DCAApp = AppContract<DCAError, Empty, DCAExecuteMsg, DCAQueryMsg, Empty>;
+pub type DCAApp =
+    AppContract<DCAError, AppInstantiateMsg, DCAExecuteMsg, DCAQueryMsg, Empty, Empty, DCASudoMsg>;
 
-const DCA_APP: DCAApp = DCAApp::new(DCA_APP_ID, DCA_APP_VERSION, None)
-    .with_instantiate(handlers::instantiate_handler)
-    .with_execute(handlers::execute_handler)
-    .with_query(handlers::query_handler)
-    .with_dependencies(&[
-        // QUEST #0
-        // This module application is dependent on two other modules: the CronCat and the Dex module.
-        // Find out how to add the DEX adapter dependency for this module.
-        StaticDependency::new(CRONCAT_ID, &[CRONCAT_MODULE_VERSION]),
+const DCA_APP: DCAApp = {
+    let app = DCAApp::new(DCA_APP_ID, DCA_APP_VERSION, None)
+        .with_instantiate(handlers::instantiate_handler)
+        .with_execute(handlers::execute_handler)
+        .with_query(handlers::query_handler)
+        .with_migrate(handlers::migrate_handler)
+        .with_replies(&[(SWAP_REPLY_ID, replies::swap_reply)])
+        .with_dependencies(&[
+            StaticDependency::new(CRONCAT_ID, &[CRONCAT_MODULE_VERSION]),
+            StaticDependency::new(
+                abstract_dex_adapter::DEX_ADAPTER_ID,
+                &[abstract_dex_adapter::contract::CONTRACT_VERSION],
+            ),
+        ]);
+    // Chain cron module triggers conversions through sudo, failed ones are recorded in a reply
+    #[cfg(feature = "chain-cron")]
+    let app = app.with_sudo(handlers::sudo_handler).with_replies(&[
+        (SWAP_REPLY_ID, replies::swap_reply),
+        (replies::CRON_CONVERT_REPLY_ID, replies::cron_convert_reply),
     ]);
+    app
+};
 
 // Export handlers
 #[cfg(feature = "export")]
@@ -1,11 +1,14 @@
+use abstract_app::abstract_core::objects::{AssetEntry, DexName};
 use abstract_app::abstract_core::AbstractError;
 use abstract_app::abstract_sdk::AbstractSdkError;
 use abstract_app::AppError as AbstractAppError;
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError, Uint128};
 use cw_asset::AssetError;
 use cw_controllers::AdminError;
 use thiserror::Error;
 
+use crate::state::DCAId;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum DCAError {
     #[error("{0}")]
@@ -31,4 +34,83 @@ pub enum DCAError {
 
     #[error("Native asset should be native")]
     NotNativeAsset {},
+
+    #[error("DCA budget and max executions must be greater than zero")]
+    ZeroLimit {},
+
+    #[error("DCA limit is already reached")]
+    LimitReached {},
+
+    #[error("DCA boundary should start before it ends and end in the future")]
+    InvalidBoundary {},
+
+    #[error("Max spread can't be greater than 100%")]
+    InvalidMaxSpread {},
+
+    #[error("DCA is paused")]
+    DCAPaused {},
+
+    #[error("DCA is completed")]
+    DCACompleted {},
+
+    #[error("DCA is expired")]
+    DCAExpired {},
+
+    #[error("Only paused DCA can be resumed")]
+    DCANotPaused {},
+
+    #[error("DCA is not due for conversion yet")]
+    NotDue {},
+
+    #[error("Keeper and chain cron schedules support only block frequency")]
+    ScheduleFrequencyUnsupported {},
+
+    #[error("Only chain cron can execute convert of this DCA")]
+    NotChainCronConvert {},
+
+    #[error("Fee rate can't be greater than {max}")]
+    FeeTooHigh { max: Decimal },
+
+    #[error("Fee recipient must be set to charge a fee")]
+    MissingFeeRecipient {},
+
+    #[error("Sender is neither the admin nor an operator allowed to {action}")]
+    Unauthorized { action: String },
+
+    #[error("Block interval can't be zero")]
+    ZeroBlockInterval {},
+
+    #[error("Invalid cron expression: {expression}")]
+    InvalidCronExpression { expression: String },
+
+    #[error("Single purchase can't be scheduled in the past")]
+    ExecutionTimeInPast {},
+
+    #[error("Pair {source_asset}/{target_asset} not found on {dex}")]
+    PairNotFound {
+        source_asset: AssetEntry,
+        target_asset: AssetEntry,
+        dex: DexName,
+    },
+
+    #[error("Swap simulation failed: {reason}")]
+    SimulationFailed { reason: String },
+
+    #[error("Source amount can't be zero")]
+    ZeroAmount {},
+
+    #[error("DCA {} not found", .dca_id.0)]
+    DCANotFound { dca_id: DCAId },
+
+    #[error("Insufficient source balance: needed {needed}, available {available}")]
+    InsufficientSourceBalance { needed: Uint128, available: Uint128 },
+
+    #[error("CronCat task balance is missing")]
+    TaskBalanceMissing {},
+
+    #[error("Failure tolerance must be greater than zero")]
+    ZeroFailureTolerance {},
+
+    #[error("Gas limit must be between {min} and {max}")]
+    GasLimitOutOfBounds { min: u64, max: u64 },
 }
@@ -1,63 +1,178 @@
 #![allow(clippy::too_many_arguments)]
 
-use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexName};
+use std::str::FromStr;
+
+use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexAssetPairing, DexName};
 use abstract_app::abstract_sdk::{
-    features::{AbstractNameService, AbstractResponse},
-    AbstractSdkResult,
+    features::{AbstractNameService, AbstractResponse, AccountIdentification},
+    Execution, TransferInterface,
 };
 use abstract_dex_adapter::api::DexInterface;
-use cosmwasm_std::{wasm_execute, CosmosMsg, Decimal, DepsMut, Empty, Env, MessageInfo, Response, Uint128};
-use croncat_app::{
-    croncat_integration_utils::{CronCatAction, CronCatTaskRequest},
-    CronCat, CronCatInterface,
+use cosmwasm_std::{
+    coins, Addr, BlockInfo, Decimal, Deps, DepsMut, Env, MessageInfo, Order, StdResult, SubMsg,
+    Uint128,
 };
-use cw_asset::{Asset, AssetInfoBase, AssetList};
+use cw_asset::{Asset, AssetInfoBase};
 
 use crate::{
     contract::{AppResult, DCAApp},
     error::DCAError,
-    msg::{DCAExecuteMsg, ExecuteMsg, Frequency},
-    state::{Config, DCAEntry, DCAId, CONFIG, DCA_LIST, NEXT_ID},
+    msg::{
+        DCABoundary, DCAExecuteMsg, DCALimit, ExecutionTime, FailurePolicy, Frequency,
+        OperatorPermissions, PriceLimit, Schedule,
+    },
+    replies::SWAP_REPLY_ID,
+    scheduler::scheduler,
+    state::{
+        load_dca, reset_task_usage, Config, DCAEntry, DCAId, DCAStatus, PendingConvert, CONFIG,
+        DCA_HISTORY, DCA_LIST, DCA_STATS, MAX_FEE_RATE, MAX_GAS_LIMIT, MIN_GAS_LIMIT, NEXT_ID,
+        OPERATORS, PENDING_CONVERT, TASK_USAGE,
+    },
 };
 
-/// Helper to for task creation message
-fn create_convert_task_internal(
-    env: Env,
-    dca: DCAEntry,
-    dca_id: DCAId,
-    cron_cat: CronCat<DCAApp>,
-    config: Config,
-) -> AbstractSdkResult<CosmosMsg> {
-    let interval = dca.frequency.to_interval();
-    let task = CronCatTaskRequest {
-        interval,
-        boundary: None,
-        stop_on_fail: true,
-        actions: vec![CronCatAction {
-            msg: wasm_execute(
-                env.contract.address,
-                // QUEST #3.1
-                // With the macro from 3.0, we implement the `From` trait that converts our custom message into the top-level message.
-                // We want to call this contract's `Convert` method but need to wrap it in the `ExecuteMsg` enum that we generated
-                // in the last quest.
-                &Empty {},
-                vec![],
-            )?
-            .into(),
-            gas_limit: Some(300_000),
-        }],
-        queries: None,
-        transforms: None,
-        cw20: None,
+/// Boundary should start before it ends and can't end in the past
+fn validate_boundary(boundary: &DCABoundary, block: &BlockInfo) -> AppResult<()> {
+    let valid = match boundary {
+        DCABoundary::Height { start, end } => match (start, end) {
+            (Some(start), Some(end)) if start >= end => false,
+            (_, Some(end)) => *end > block.height,
+            _ => true,
+        },
+        DCABoundary::Time { start, end } => match (start, end) {
+            (Some(start), Some(end)) if start >= end => false,
+            (_, Some(end)) => *end > block.time,
+            _ => true,
+        },
     };
-    let _assets = AssetList::from(vec![Asset::native(
-        config.native_denom,
-        config.dca_creation_amount,
-    )]);
-    // QUEST #2.3
-    // Generate create task message
-    // Croncat API: https://github.com/AbstractSDK/abstract/blob/main/modules/contracts/apps/croncat/src/api.rs
-    todo!()
+    if !valid {
+        return Err(DCAError::InvalidBoundary {});
+    }
+    Ok(())
+}
+
+/// Max spread override can't be greater than 100%
+fn validate_max_spread(max_spread: Option<Decimal>) -> AppResult<()> {
+    if max_spread.map_or(false, |max_spread| max_spread > Decimal::one()) {
+        return Err(DCAError::InvalidMaxSpread {});
+    }
+    Ok(())
+}
+
+/// Admin can do anything, operators only what they are permitted
+fn assert_admin_or_operator(
+    deps: Deps,
+    app: &DCAApp,
+    sender: &Addr,
+    action: &str,
+    permitted: fn(&OperatorPermissions) -> bool,
+) -> AppResult<()> {
+    if app.admin.is_admin(deps, sender)? {
+        return Ok(());
+    }
+    match OPERATORS.may_load(deps.storage, sender)? {
+        Some(permissions) if permitted(&permissions) => Ok(()),
+        _ => Err(DCAError::Unauthorized {
+            action: action.to_owned(),
+        }),
+    }
+}
+
+/// Fee is bounded and needs someone to receive it
+fn validate_fee(config: &Config) -> AppResult<()> {
+    if config.fee_rate > MAX_FEE_RATE {
+        return Err(DCAError::FeeTooHigh { max: MAX_FEE_RATE });
+    }
+    if !config.fee_rate.is_zero() && config.fee_recipient.is_none() {
+        return Err(DCAError::MissingFeeRecipient {});
+    }
+    Ok(())
+}
+
+/// Gas limit has to fit a convert with history and reply, but not waste the task deposit
+fn validate_gas_limit(gas_limit: u64) -> AppResult<()> {
+    if !(MIN_GAS_LIMIT..=MAX_GAS_LIMIT).contains(&gas_limit) {
+        return Err(DCAError::GasLimitOutOfBounds {
+            min: MIN_GAS_LIMIT,
+            max: MAX_GAS_LIMIT,
+        });
+    }
+    Ok(())
+}
+
+/// Pair has to be registered for the dex in ANS and swappable
+fn validate_swap(
+    deps: Deps,
+    app: &DCAApp,
+    source_asset: &AnsAsset,
+    target_asset: &AssetEntry,
+    dex: &DexName,
+) -> AppResult<()> {
+    if source_asset.amount.is_zero() {
+        return Err(DCAError::ZeroAmount {});
+    }
+
+    let pair_not_found = || DCAError::PairNotFound {
+        source_asset: source_asset.name.clone(),
+        target_asset: target_asset.clone(),
+        dex: dex.clone(),
+    };
+    let pairing = DexAssetPairing::new(source_asset.name.clone(), target_asset.clone(), dex);
+    let pools = app
+        .name_service(deps)
+        .query(&pairing)
+        .map_err(|_| pair_not_found())?;
+    if pools.is_empty() {
+        return Err(pair_not_found());
+    }
+
+    app.dex(deps, dex.clone())
+        .simulate_swap(source_asset.clone(), target_asset.clone())
+        .map_err(|err| DCAError::SimulationFailed {
+            reason: err.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Frequency has to be valid for CronCat to schedule it
+fn validate_frequency(frequency: &Frequency, block: &BlockInfo) -> AppResult<()> {
+    match frequency {
+        Frequency::EveryNBlocks(0) => Err(DCAError::ZeroBlockInterval {}),
+        Frequency::EveryNBlocks(_) | Frequency::Immediate => Ok(()),
+        Frequency::Once(ExecutionTime::Height(height)) if *height <= block.height => {
+            Err(DCAError::ExecutionTimeInPast {})
+        }
+        Frequency::Once(ExecutionTime::Time(time)) if *time <= block.time => {
+            Err(DCAError::ExecutionTimeInPast {})
+        }
+        Frequency::Once(_) => Ok(()),
+        Frequency::Cron(expression) => cron_schedule::Schedule::from_str(expression)
+            .map(|_| ())
+            .map_err(|_| DCAError::InvalidCronExpression {
+                expression: expression.clone(),
+            }),
+    }
+}
+
+/// Only CronCat can follow cron expressions, other schedules track block frequency
+fn validate_schedule(dca: &DCAEntry) -> AppResult<()> {
+    if dca.schedule != Schedule::CronCat && !matches!(dca.frequency, Frequency::EveryNBlocks(_)) {
+        return Err(DCAError::ScheduleFrequencyUnsupported {});
+    }
+    Ok(())
+}
+
+/// Budget, execution and failure limits can't be zero, budget and executions can't be already exhausted
+fn validate_limits(dca: &DCAEntry) -> AppResult<()> {
+    if dca.budget == Some(Uint128::zero()) || dca.max_executions == Some(0) {
+        return Err(DCAError::ZeroLimit {});
+    }
+    if dca.failure_policy == FailurePolicy::PauseAfter(0) {
+        return Err(DCAError::ZeroFailureTolerance {});
+    }
+    if dca.limit_reached() {
+        return Err(DCAError::LimitReached {});
+    }
+    Ok(())
 }
 
 pub fn execute_handler(
@@ -73,6 +188,13 @@ pub fn execute_handler(
             new_dca_task_balance,
             task_refill_threshold,
             max_spread,
+            keeper_tip,
+            default_schedule,
+            fee_rate,
+            fee_recipient,
+            gas_limit,
+            refill_executions,
+            max_refill_amount,
         } => update_config(
             deps,
             info,
@@ -81,12 +203,27 @@ pub fn execute_handler(
             new_dca_task_balance,
             task_refill_threshold,
             max_spread,
+            keeper_tip,
+            default_schedule,
+            fee_rate,
+            fee_recipient,
+            gas_limit,
+            refill_executions,
+            max_refill_amount,
         ),
         DCAExecuteMsg::CreateDCA {
             source_asset,
             target_asset,
             frequency,
             dex,
+            boundary,
+            budget,
+            max_executions,
+            max_spread,
+            price_limit,
+            schedule,
+            failure_policy,
+            gas_limit,
         } => create_dca(
             deps,
             env,
@@ -96,6 +233,14 @@ pub fn execute_handler(
             target_asset,
             frequency,
             dex,
+            boundary,
+            budget,
+            max_executions,
+            max_spread,
+            price_limit,
+            schedule,
+            failure_policy,
+            gas_limit,
         ),
         DCAExecuteMsg::UpdateDCA {
             dca_id,
@@ -103,6 +248,14 @@ pub fn execute_handler(
             new_target_asset,
             new_frequency,
             new_dex,
+            new_boundary,
+            new_budget,
+            new_max_executions,
+            new_max_spread,
+            new_price_limit,
+            new_failure_policy,
+            new_gas_limit,
+            clear_limits,
         } => update_dca(
             deps,
             env,
@@ -113,8 +266,23 @@ pub fn execute_handler(
             new_target_asset,
             new_frequency,
             new_dex,
+            new_boundary,
+            new_budget,
+            new_max_executions,
+            new_max_spread,
+            new_price_limit,
+            new_failure_policy,
+            new_gas_limit,
+            clear_limits.unwrap_or_default(),
         ),
         DCAExecuteMsg::CancelDCA { dca_id } => cancel_dca(deps, info, app, dca_id),
+        DCAExecuteMsg::SetOperator {
+            operator,
+            permissions,
+        } => set_operator(deps, info, app, operator, permissions),
+        DCAExecuteMsg::RemoveOperator { operator } => remove_operator(deps, info, app, operator),
+        DCAExecuteMsg::PauseDCA { dca_id } => pause_dca(deps, info, app, dca_id),
+        DCAExecuteMsg::ResumeDCA { dca_id } => resume_dca(deps, env, info, app, dca_id),
         DCAExecuteMsg::Convert { dca_id } => convert(deps, env, info, app, dca_id),
     }
 }
@@ -122,16 +290,22 @@ pub fn execute_handler(
 /// Update the configuration of the app
 fn update_config(
     deps: DepsMut,
-    _msg_info: MessageInfo,
+    msg_info: MessageInfo,
     app: DCAApp,
     new_native_asset: Option<AssetEntry>,
     new_dca_creation_amount: Option<Uint128>,
     new_refill_threshold: Option<Uint128>,
     new_max_spread: Option<Decimal>,
+    new_keeper_tip: Option<Uint128>,
+    new_default_schedule: Option<Schedule>,
+    new_fee_rate: Option<Decimal>,
+    new_fee_recipient: Option<String>,
+    new_gas_limit: Option<u64>,
+    new_refill_executions: Option<u32>,
+    new_max_refill_amount: Option<Uint128>,
 ) -> AppResult {
-    // QUEST #1
-    // Only the admin should be able to call this so add an admin assertion.
-    // Hint: https://docs.rs/abstract-app/0.17.0/abstract_app/state/struct.AppContract.html#
+    // Only the admin should be able to call this
+    app.admin.assert_admin(deps.as_ref(), &msg_info.sender)?;
 
     let old_config = CONFIG.load(deps.storage)?;
     let new_native_denom = new_native_asset
@@ -144,16 +318,26 @@ fn update_config(
             }
         })
         .transpose()?;
+    let new_fee_recipient = new_fee_recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            native_denom: new_native_denom.unwrap_or(old_config.native_denom),
-            dca_creation_amount: new_dca_creation_amount.unwrap_or(old_config.dca_creation_amount),
-            refill_threshold: new_refill_threshold.unwrap_or(old_config.refill_threshold),
-            max_spread: new_max_spread.unwrap_or(old_config.max_spread),
-        },
-    )?;
+    let new_config = Config {
+        native_denom: new_native_denom.unwrap_or(old_config.native_denom),
+        dca_creation_amount: new_dca_creation_amount.unwrap_or(old_config.dca_creation_amount),
+        refill_threshold: new_refill_threshold.unwrap_or(old_config.refill_threshold),
+        max_spread: new_max_spread.unwrap_or(old_config.max_spread),
+        keeper_tip: new_keeper_tip.unwrap_or(old_config.keeper_tip),
+        default_schedule: new_default_schedule.unwrap_or(old_config.default_schedule),
+        fee_rate: new_fee_rate.unwrap_or(old_config.fee_rate),
+        fee_recipient: new_fee_recipient.or(old_config.fee_recipient),
+        gas_limit: new_gas_limit.unwrap_or(old_config.gas_limit),
+        refill_executions: new_refill_executions.unwrap_or(old_config.refill_executions),
+        max_refill_amount: new_max_refill_amount.or(old_config.max_refill_amount),
+    };
+    validate_fee(&new_config)?;
+    validate_gas_limit(new_config.gas_limit)?;
+    CONFIG.save(deps.storage, &new_config)?;
 
     Ok(app.response("update_config"))
 }
@@ -168,18 +352,23 @@ fn create_dca(
     target_asset: AssetEntry,
     frequency: Frequency,
     dex_name: DexName,
+    boundary: Option<DCABoundary>,
+    budget: Option<Uint128>,
+    max_executions: Option<u64>,
+    max_spread: Option<Decimal>,
+    price_limit: Option<PriceLimit>,
+    schedule: Option<Schedule>,
+    failure_policy: Option<FailurePolicy>,
+    gas_limit: Option<u64>,
 ) -> AppResult {
-    // Only the admin should be able to create dca
-    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    // Only the admin or permitted operators should be able to create dca
+    assert_admin_or_operator(deps.as_ref(), &app, &info.sender, "create", |permissions| {
+        permissions.create
+    })?;
 
     let config = CONFIG.load(deps.storage)?;
 
-    // QUEST #2.1
-    // Here we want to validate that a swap can be performed between the two assets.
-    // We can check this by doing a swap simulation using the DEX API
-    // If the simulation fails, we should return an error
-    // What is an API: https://docs.abstract.money/4_get_started/4_sdk.html
-    // The Dex API: https://github.com/AbstractSDK/abstract/blob/main/modules/contracts/adapters/dex/src/api.rs
+    validate_swap(deps.as_ref(), &app, &source_asset, &target_asset, &dex_name)?;
 
     // Generate DCA ID
     let dca_id = NEXT_ID.update(deps.storage, |id| AppResult::Ok(id.next_id()))?;
@@ -189,15 +378,39 @@ fn create_dca(
         target_asset,
         frequency,
         dex: dex_name,
+        boundary,
+        budget,
+        max_executions,
+        max_spread,
+        price_limit,
+        spent: Uint128::zero(),
+        executions: 0,
+        status: DCAStatus::Active,
+        schedule: schedule.unwrap_or_else(|| config.default_schedule.clone()),
+        last_convert_height: None,
+        last_convert_time: None,
+        failure_policy: failure_policy.unwrap_or_default(),
+        consecutive_failures: 0,
+        gas_limit,
     };
+    validate_limits(&dca_entry)?;
+    if let Some(gas_limit) = dca_entry.gas_limit {
+        validate_gas_limit(gas_limit)?;
+    }
+    validate_frequency(&dca_entry.frequency, &env.block)?;
+    validate_schedule(&dca_entry)?;
+    validate_max_spread(dca_entry.max_spread)?;
+    if let Some(boundary) = &dca_entry.boundary {
+        validate_boundary(boundary, &env.block)?;
+    }
     DCA_LIST.save(deps.storage, dca_id, &dca_entry)?;
 
-    // QUEST #2.0
-    // Pass on the Cron Cat API: https://github.com/AbstractSDK/abstract/blob/main/modules/contracts/apps/croncat/src/api.rs
-    // to generate the cron task message.
+    let scheduler = scheduler(deps.as_ref(), &app, &dca_entry.schedule);
+    let task_msgs = scheduler.create_task(&env, dca_id, &dca_entry, &config)?;
 
     Ok(app
         .response("create_dca")
+        .add_messages(task_msgs)
         .add_attribute("dca_id", dca_id))
 }
 
@@ -212,87 +425,425 @@ fn update_dca(
     new_target_asset: Option<AssetEntry>,
     new_frequency: Option<Frequency>,
     new_dex: Option<DexName>,
+    new_boundary: Option<DCABoundary>,
+    new_budget: Option<Uint128>,
+    new_max_executions: Option<u64>,
+    new_max_spread: Option<Decimal>,
+    new_price_limit: Option<PriceLimit>,
+    new_failure_policy: Option<FailurePolicy>,
+    new_gas_limit: Option<u64>,
+    clear_limits: Vec<DCALimit>,
 ) -> AppResult {
-    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+    assert_admin_or_operator(deps.as_ref(), &app, &info.sender, "update", |permissions| {
+        permissions.update
+    })?;
 
-    // Only if frequency is changed we have to re-create a task
-    let recreate_task = new_frequency.is_some();
+    // Only if frequency, boundary or gas limit is changed we have to re-create a task
+    let recreate_task = new_frequency.is_some()
+        || new_boundary.is_some()
+        || clear_limits.contains(&DCALimit::Boundary)
+        || new_gas_limit.is_some();
+    if let Some(frequency) = &new_frequency {
+        validate_frequency(frequency, &env.block)?;
+    }
+    if let Some(boundary) = &new_boundary {
+        validate_boundary(boundary, &env.block)?;
+    }
+    if let Some(gas_limit) = new_gas_limit {
+        validate_gas_limit(gas_limit)?;
+    }
 
-    let old_dca = DCA_LIST.load(deps.storage, dca_id)?;
-    let new_dca = DCAEntry {
+    let mut old_dca = load_dca(deps.storage, dca_id)?;
+    for limit in clear_limits {
+        match limit {
+            DCALimit::Boundary => old_dca.boundary = None,
+            DCALimit::Budget => old_dca.budget = None,
+            DCALimit::MaxExecutions => old_dca.max_executions = None,
+            DCALimit::PriceLimit => old_dca.price_limit = None,
+        }
+    }
+    // Single purchase is done, nothing left to update
+    if old_dca.status == DCAStatus::Completed && old_dca.frequency.is_one_shot() {
+        return Err(DCAError::DCACompleted {});
+    }
+    // Completed DCA gets a new task if its limits were raised,
+    // expired one-shot DCA gets another try
+    let reactivate = matches!(old_dca.status, DCAStatus::Completed | DCAStatus::Expired);
+    let mut new_dca = DCAEntry {
         source_asset: new_source_asset.unwrap_or(old_dca.source_asset),
         target_asset: new_target_asset.unwrap_or(old_dca.target_asset),
         frequency: new_frequency.unwrap_or(old_dca.frequency),
         dex: new_dex.unwrap_or(old_dca.dex),
+        boundary: new_boundary.or(old_dca.boundary),
+        budget: new_budget.or(old_dca.budget),
+        max_executions: new_max_executions.or(old_dca.max_executions),
+        max_spread: new_max_spread.or(old_dca.max_spread),
+        price_limit: new_price_limit.or(old_dca.price_limit),
+        failure_policy: new_failure_policy.unwrap_or(old_dca.failure_policy),
+        gas_limit: new_gas_limit.or(old_dca.gas_limit),
+        ..old_dca
     };
+    validate_limits(&new_dca)?;
+    validate_schedule(&new_dca)?;
+    validate_max_spread(new_dca.max_spread)?;
+    if reactivate {
+        // Execution time of unchanged frequency may be in the past by now
+        validate_frequency(&new_dca.frequency, &env.block)?;
+        new_dca.status = DCAStatus::Active;
+    }
 
-    // QUEST #2.2 (same as 2.1)
-    // Here we want to validate that a swap can be performed between the two assets.
-    // We can check this by doing a swap simulation using the DEX API
-    // If the simulation fails, we should return an error
-    // Simulate swap for a new dca
+    validate_swap(
+        deps.as_ref(),
+        &app,
+        &new_dca.source_asset,
+        &new_dca.target_asset,
+        &new_dca.dex,
+    )?;
 
     DCA_LIST.save(deps.storage, dca_id, &new_dca)?;
+    if reactivate || recreate_task {
+        reset_task_usage(deps.storage, dca_id)?;
+    }
 
+    let config = CONFIG.load(deps.storage)?;
+    let scheduler = scheduler(deps.as_ref(), &app, &new_dca.schedule);
     let response = app.response("update_dca");
-    let response = if recreate_task {
-        let config = CONFIG.load(deps.storage)?;
-        let cron_cat = app.cron_cat(deps.as_ref());
-        let remove_task_msg = cron_cat.remove_task(dca_id)?;
-        let create_task_msg = create_convert_task_internal(env, new_dca, dca_id, cron_cat, config)?;
-        response.add_messages(vec![remove_task_msg, create_task_msg])
+    // Paused DCA has no task, new settings are used on resume
+    let response = if reactivate {
+        response.add_messages(scheduler.create_task(&env, dca_id, &new_dca, &config)?)
+    } else if recreate_task && new_dca.status == DCAStatus::Active {
+        response
+            .add_messages(scheduler.remove_task(dca_id)?)
+            .add_messages(scheduler.create_task(&env, dca_id, &new_dca, &config)?)
     } else {
         response
     };
     Ok(response)
 }
 
-/// Remove existing dca, remove task from cron_cat
+/// Remove existing dca, remove its task
 fn cancel_dca(deps: DepsMut, info: MessageInfo, app: DCAApp, dca_id: DCAId) -> AppResult {
+    assert_admin_or_operator(deps.as_ref(), &app, &info.sender, "cancel", |permissions| {
+        permissions.cancel
+    })?;
+
+    let dca = load_dca(deps.storage, dca_id)?;
+    DCA_LIST.remove(deps.storage, dca_id)?;
+
+    let executions = DCA_HISTORY
+        .prefix(dca_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for execution in executions {
+        DCA_HISTORY.remove(deps.storage, (dca_id, execution));
+    }
+    DCA_STATS.remove(deps.storage, dca_id);
+    TASK_USAGE.remove(deps.storage, dca_id);
+
+    let response = app.response("cancel_dca");
+    // Task of paused or completed DCA is already removed
+    let response = if dca.status == DCAStatus::Active {
+        let scheduler = scheduler(deps.as_ref(), &app, &dca.schedule);
+        response.add_messages(scheduler.remove_task(dca_id)?)
+    } else {
+        response
+    };
+    Ok(response)
+}
+
+/// Pause dca, remove its task
+/// Entry, its id and history are kept
+fn pause_dca(deps: DepsMut, info: MessageInfo, app: DCAApp, dca_id: DCAId) -> AppResult {
+    assert_admin_or_operator(deps.as_ref(), &app, &info.sender, "pause", |permissions| {
+        permissions.pause
+    })?;
+
+    let mut dca = load_dca(deps.storage, dca_id)?;
+    match dca.status {
+        DCAStatus::Active => {}
+        DCAStatus::Paused => return Err(DCAError::DCAPaused {}),
+        DCAStatus::Completed => return Err(DCAError::DCACompleted {}),
+        DCAStatus::Expired => return Err(DCAError::DCAExpired {}),
+    }
+    dca.status = DCAStatus::Paused;
+    DCA_LIST.save(deps.storage, dca_id, &dca)?;
+
+    let scheduler = scheduler(deps.as_ref(), &app, &dca.schedule);
+    Ok(app
+        .response("pause_dca")
+        .add_messages(scheduler.remove_task(dca_id)?)
+        .add_attribute("dca_id", dca_id))
+}
+
+/// Resume paused dca with the same settings, re-create its task
+fn resume_dca(deps: DepsMut, env: Env, info: MessageInfo, app: DCAApp, dca_id: DCAId) -> AppResult {
+    assert_admin_or_operator(deps.as_ref(), &app, &info.sender, "resume", |permissions| {
+        permissions.pause
+    })?;
+
+    let mut dca = load_dca(deps.storage, dca_id)?;
+    match dca.status {
+        DCAStatus::Paused => {}
+        DCAStatus::Active => return Err(DCAError::DCANotPaused {}),
+        DCAStatus::Completed => return Err(DCAError::DCACompleted {}),
+        DCAStatus::Expired => return Err(DCAError::DCAExpired {}),
+    }
+    dca.status = DCAStatus::Active;
+    dca.consecutive_failures = 0;
+    DCA_LIST.save(deps.storage, dca_id, &dca)?;
+    reset_task_usage(deps.storage, dca_id)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let scheduler = scheduler(deps.as_ref(), &app, &dca.schedule);
+    let task_msgs = scheduler.create_task(&env, dca_id, &dca, &config)?;
+    Ok(app
+        .response("resume_dca")
+        .add_messages(task_msgs)
+        .add_attribute("dca_id", dca_id))
+}
+
+/// Grant operator permissions to manage DCAs
+fn set_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    app: DCAApp,
+    operator: String,
+    permissions: OperatorPermissions,
+) -> AppResult {
+    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.save(deps.storage, &operator, &permissions)?;
+
+    Ok(app
+        .response("set_operator")
+        .add_attribute("operator", operator))
+}
+
+/// Revoke operator permissions
+fn remove_operator(deps: DepsMut, info: MessageInfo, app: DCAApp, operator: String) -> AppResult {
     app.admin.assert_admin(deps.as_ref(), &info.sender)?;
 
-    // QUEST #2.4
-    // Remove task from Cron Cat
-    DCA_LIST.remove(deps.storage, dca_id.clone());
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, &operator);
 
-    Ok(app.response("cancel_dca"))
+    Ok(app
+        .response("remove_operator")
+        .add_attribute("operator", operator))
 }
 
-/// Execute swap if called my croncat manager
+/// Execute swap if called by the scheduler of the DCA
 /// Refill task if needed
+/// Swap reply removes task once the DCA reaches its limits
 fn convert(deps: DepsMut, env: Env, info: MessageInfo, app: DCAApp, dca_id: DCAId) -> AppResult {
-    let cron_cat = app.cron_cat(deps.as_ref());
+    let config = CONFIG.load(deps.storage)?;
+    let mut dca = load_dca(deps.storage, dca_id)?;
+    let scheduler = scheduler(deps.as_ref(), &app, &dca.schedule);
+    scheduler.assert_caller(&env, dca_id, &dca, &info.sender)?;
 
-    let manager_addr = cron_cat.query_manager_addr(env.contract.address.clone(), dca_id)?;
-    if manager_addr != info.sender {
-        return Err(DCAError::NotManagerConvert {});
+    // Paused DCA has its task removed, but make sure nothing gets converted
+    match dca.status {
+        DCAStatus::Active => {}
+        DCAStatus::Paused => {
+            return Ok(app
+                .response("convert")
+                .add_attribute("dca_id", dca_id)
+                .add_attribute("skipped", "paused"))
+        }
+        DCAStatus::Completed => return Err(DCAError::DCACompleted {}),
+        DCAStatus::Expired => return Err(DCAError::DCAExpired {}),
     }
 
-    let config = CONFIG.load(deps.storage)?;
-    let dca = DCA_LIST.load(deps.storage, dca_id)?;
+    let amount = dca.next_amount();
+    let bank = app.bank(deps.as_ref());
+    let source_balance = bank.balance(&dca.source_asset.name)?.amount;
+    // Fee is taken from the source amount, the rest is swapped
+    let fee = amount * config.fee_rate;
+    let offer_asset = AnsAsset::new(dca.source_asset.name.clone(), amount - fee);
+    let dex = app.dex(deps.as_ref(), dca.dex.clone());
+
+    // Skip this purchase without failing the task if the price is worse than the limit
+    // Fee counts towards the price
+    // Missing source funds and failed simulation count as a failed swap,
+    // so the failure policy can stop a DCA that can't purchase
+    let price_check = match &dca.price_limit {
+        _ if source_balance < amount => Err(DCAError::InsufficientSourceBalance {
+            needed: amount,
+            available: source_balance,
+        }
+        .to_string()),
+        Some(price_limit) => dex
+            .simulate_swap(offer_asset.clone(), dca.target_asset.clone())
+            .map(|simulation| price_limit.is_satisfied(amount, simulation.return_amount))
+            .map_err(|err| err.to_string()),
+        None => Ok(true),
+    };
+    let price_satisfied = price_check == Ok(true);
+
+    dca.last_convert_height = Some(env.block.height);
+    dca.last_convert_time = Some(env.block.time);
 
     let mut messages = vec![];
+    let mut pending_convert = None;
+    let mut task_usage = None;
+    // Native funds sent to the task before the swap
+    let mut task_funding = Uint128::zero();
+    let mut stats = DCA_STATS
+        .may_load(deps.storage, dca_id)?
+        .unwrap_or_default();
+    let response = app.response("convert").add_attribute("dca_id", dca_id);
+
+    // One-shot DCA runs once, it expires if it can't purchase now,
+    // otherwise the swap reply completes it
+    let one_shot = dca.frequency.is_one_shot();
+    if one_shot && !price_satisfied {
+        dca.status = DCAStatus::Expired;
+    }
+    let paused = price_check.is_err() && dca.record_failure();
 
-    // In case task running out of balance - refill it
-    let task_balance = cron_cat
-        .query_task_balance(env.contract.address, dca_id)?
-        .balance
-        .unwrap();
-    if task_balance.native_balance < config.refill_threshold {
-        messages.push(
-            cron_cat.refill_task(
+    let response = if one_shot {
+        // CronCat removes one-shot tasks after they run
+        if price_satisfied {
+            response
+        } else {
+            response.add_attribute("dca_status", "expired")
+        }
+    } else if paused {
+        messages.extend(scheduler.remove_task(dca_id)?.into_iter().map(SubMsg::new));
+        response.add_attribute("dca_status", "paused")
+    } else if price_satisfied && dca.completes_with(amount) {
+        // Swap reply removes the task, nothing to refill
+        response
+    } else {
+        // Sent before the swap, so the swap reply can remove the task
+        match scheduler.task_balance(&env, dca_id) {
+            Ok(None) => response,
+            Ok(Some(task_balance)) => {
+                let mut usage = TASK_USAGE
+                    .may_load(deps.storage, dca_id)?
+                    .unwrap_or_default();
+                usage.observe(task_balance);
+                // In case task running out of balance - refill it
+                // for the next executions at the observed consumption
+                let refill_amount = config.refill_amount(usage.per_execution);
+                let response = if task_balance < config.refill_threshold && !refill_amount.is_zero()
+                {
+                    messages.extend(
+                        scheduler
+                            .refill_task(dca_id, &config, refill_amount)?
+                            .into_iter()
+                            .map(SubMsg::new),
+                    );
+                    usage.last_balance = Some(task_balance + refill_amount);
+                    task_funding = refill_amount;
+                    response.add_attribute("refill", refill_amount)
+                } else {
+                    response
+                };
+                task_usage = Some(usage);
+                response
+            }
+            // Task expired together with the boundary, nothing to recreate
+            Err(DCAError::TaskBalanceMissing {})
+                if dca
+                    .boundary
+                    .as_ref()
+                    .map_or(false, |boundary| boundary.has_ended(&env.block)) =>
+            {
+                response
+            }
+            // Task is gone, recreate it so the DCA keeps running
+            Err(DCAError::TaskBalanceMissing {}) => {
+                let proxy = app.proxy_address(deps.as_ref())?;
+                let available = deps
+                    .querier
+                    .query_balance(proxy, &config.native_denom)?
+                    .amount;
+                if available < config.dca_creation_amount {
+                    return Err(DCAError::TaskBalanceMissing {});
+                }
+                messages.extend(
+                    scheduler
+                        .remove_task(dca_id)?
+                        .into_iter()
+                        .chain(scheduler.create_task(&env, dca_id, &dca, &config)?)
+                        .map(SubMsg::new),
+                );
+                let mut usage = TASK_USAGE
+                    .may_load(deps.storage, dca_id)?
+                    .unwrap_or_default();
+                usage.last_balance = None;
+                task_usage = Some(usage);
+                task_funding = config.dca_creation_amount;
+                response.add_attribute("task", "recreated")
+            }
+            Err(err) => return Err(err),
+        }
+    };
+
+    let response = match price_check {
+        Ok(true) => {
+            // Swap reply compares balances to record actual amounts and sends the fee,
+            // or applies the failure policy if the swap fails, e.g. when task funding
+            // leaves too little of the native denom source.
+            // Task funding leaves the account before the swap, so it's not counted
+            // as spent or missing when the DCA assets are the native denom
+            let before_swap = |balance: Asset| match balance.info {
+                AssetInfoBase::Native(denom) if denom == config.native_denom => {
+                    balance.amount.saturating_sub(task_funding)
+                }
+                _ => balance.amount,
+            };
+            pending_convert = Some(PendingConvert {
                 dca_id,
-                AssetList::from(vec![Asset::native(
-                    config.native_denom,
-                    config.dca_creation_amount,
-                )])
-                .into(),
-            )?,
-        );
+                amount,
+                fee,
+                source_balance: before_swap(bank.balance(&dca.source_asset.name)?),
+                target_balance: before_swap(bank.balance(&dca.target_asset)?),
+            });
+            messages.push(SubMsg::reply_always(
+                dex.swap(
+                    offer_asset,
+                    dca.target_asset.clone(),
+                    Some(dca.max_spread.unwrap_or(config.max_spread)),
+                    None,
+                )?,
+                SWAP_REPLY_ID,
+            ));
+            response.add_attribute("amount", amount)
+        }
+        Ok(false) => {
+            stats.skipped += 1;
+            response.add_attribute("skipped", "price_limit")
+        }
+        Err(reason) => {
+            stats.failed += 1;
+            response
+                .add_attribute("swap_error", reason)
+                .add_attribute("consecutive_failures", dca.consecutive_failures.to_string())
+        }
+    };
+
+    // Reward the keeper for triggering the purchase
+    let response = if dca.schedule == Schedule::Keeper && !config.keeper_tip.is_zero() {
+        let tip = app.bank(deps.as_ref()).transfer(
+            coins(config.keeper_tip.u128(), &config.native_denom),
+            &info.sender,
+        )?;
+        messages.push(SubMsg::new(app.executor(deps.as_ref()).execute(vec![tip])?));
+        response.add_attribute("keeper_tip", config.keeper_tip)
+    } else {
+        response
+    };
+
+    DCA_LIST.save(deps.storage, dca_id, &dca)?;
+    if let Some(pending_convert) = pending_convert {
+        PENDING_CONVERT.save(deps.storage, &pending_convert)?;
+    }
+    if let Some(task_usage) = task_usage {
+        TASK_USAGE.save(deps.storage, dca_id, &task_usage)?;
     }
+    DCA_STATS.save(deps.storage, dca_id, &stats)?;
 
-    // QUEST #2.5
-    // Finally do the swap!
-    
-    Ok(app.response("convert").add_messages(messages))
+    Ok(response.add_submessages(messages))
 }
@@ -1,11 +1,12 @@
 use abstract_app::abstract_sdk::features::AbstractNameService;
-use cosmwasm_std::{Addr, DepsMut, Empty, Env, MessageInfo, Response};
+use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
 use cw_asset::AssetInfoBase;
 
 use crate::{
     contract::{AppResult, DCAApp},
     error::DCAError,
-    state::{Config, CONFIG, NEXT_ID},
+    msg::{AppInstantiateMsg, Schedule},
+    state::{Config, CONFIG, DEFAULT_GAS_LIMIT, NEXT_ID},
 };
 
 pub fn instantiate_handler(
@@ -13,18 +14,28 @@ pub fn instantiate_handler(
     _env: Env,
     _info: MessageInfo,
     app: DCAApp,
-    // QUEST #3.3
-    // Replace this with the custom instantiate message type and set the `Config` object with it.
-    msg: Empty,
+    msg: AppInstantiateMsg,
 ) -> AppResult {
     let name_service = app.name_service(deps.as_ref());
-    let asset: AssetInfoBase<Addr>  = todo!("Query the ANS with the msg's native_asset to get the denom.");
+    let asset = name_service.query(&msg.native_asset)?;
     let native_denom = match asset {
         AssetInfoBase::Native(denom) => denom,
         _ => return Err(DCAError::NotNativeAsset {}),
     };
 
-    let config: Config = todo!();
+    let config: Config = Config {
+        native_denom,
+        dca_creation_amount: msg.dca_creation_amount,
+        refill_threshold: msg.refill_threshold,
+        max_spread: msg.max_spread,
+        keeper_tip: Uint128::zero(),
+        default_schedule: Schedule::default(),
+        fee_rate: Decimal::zero(),
+        fee_recipient: None,
+        gas_limit: DEFAULT_GAS_LIMIT,
+        refill_executions: 0,
+        max_refill_amount: None,
+    };
 
     CONFIG.save(deps.storage, &config)?;
     NEXT_ID.save(deps.storage, &Default::default())?;
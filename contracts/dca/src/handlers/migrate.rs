@@ -0,0 +1,64 @@
+use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexName};
+use abstract_app::abstract_sdk::features::AbstractResponse;
+use cosmwasm_std::{DepsMut, Empty, Env, Order, StdResult, Uint128};
+use cw_storage_plus::Map;
+
+use crate::{
+    contract::{AppResult, DCAApp},
+    msg::{FailurePolicy, Frequency, Schedule},
+    state::{DCAEntry, DCAId, DCAStatus, DCA_LIST},
+};
+
+/// DCA entry as it was stored before limits, boundaries and overrides were added
+#[cosmwasm_schema::cw_serde]
+struct LegacyDCAEntry {
+    source_asset: AnsAsset,
+    target_asset: AssetEntry,
+    frequency: Frequency,
+    dex: DexName,
+}
+
+impl From<LegacyDCAEntry> for DCAEntry {
+    fn from(legacy: LegacyDCAEntry) -> Self {
+        DCAEntry {
+            source_asset: legacy.source_asset,
+            target_asset: legacy.target_asset,
+            frequency: legacy.frequency,
+            dex: legacy.dex,
+            boundary: None,
+            budget: None,
+            max_executions: None,
+            max_spread: None,
+            price_limit: None,
+            spent: Uint128::zero(),
+            executions: 0,
+            status: DCAStatus::Active,
+            schedule: Schedule::CronCat,
+            last_convert_height: None,
+            last_convert_time: None,
+            failure_policy: FailurePolicy::StopOnFail,
+            consecutive_failures: 0,
+            gas_limit: None,
+        }
+    }
+}
+
+const LEGACY_DCA_LIST: Map<DCAId, LegacyDCAEntry> = Map::new("dca_list");
+
+pub fn migrate_handler(deps: DepsMut, _env: Env, app: DCAApp, _msg: Empty) -> AppResult {
+    let dca_ids = LEGACY_DCA_LIST
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Re-save every entry so secondary indexes get populated
+    for dca_id in dca_ids {
+        let dca_id = DCAId(dca_id);
+        let dca = match DCA_LIST.load(deps.storage, dca_id) {
+            Ok(dca) => dca,
+            Err(_) => LEGACY_DCA_LIST.load(deps.storage, dca_id)?.into(),
+        };
+        DCA_LIST.replace(deps.storage, dca_id, Some(&dca), None)?;
+    }
+
+    Ok(app.response("migrate"))
+}
@@ -1,7 +1,13 @@
 pub mod execute;
 pub mod instantiate;
+pub mod migrate;
 pub mod query;
+#[cfg(feature = "chain-cron")]
+pub mod sudo;
 
+#[cfg(feature = "chain-cron")]
+pub use crate::handlers::sudo::sudo_handler;
 pub use crate::handlers::{
-    execute::execute_handler, instantiate::instantiate_handler, query::query_handler,
+    execute::execute_handler, instantiate::instantiate_handler, migrate::migrate_handler,
+    query::query_handler,
 };
@@ -1,18 +1,82 @@
-use abstract_app::abstract_core::objects::DexAssetPairing;
-use abstract_app::abstract_sdk::features::AbstractNameService;
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env};
+use std::{collections::BTreeMap, str::FromStr};
+
+use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexAssetPairing, DexName};
+use abstract_app::abstract_sdk::{
+    features::{AbstractNameService, AccountIdentification},
+    TransferInterface,
+};
+use abstract_dex_adapter::api::DexInterface;
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, Env, Order, StdResult, Timestamp, Uint128,
+};
 use cw_asset::AssetInfo;
+use cw_storage_plus::Bound;
 
 use crate::{
     contract::{AppResult, DCAApp},
-    msg::{ConfigResponse, DCAQueryMsg, DCAResponse},
-    state::{DCAId, CONFIG, DCA_LIST},
+    error::DCAError,
+    msg::{
+        ConfigResponse, DCABoundary, DCAHistoryEntry, DCAHistoryResponse, DCAListEntry,
+        DCAQueryMsg, DCAResponse, DCAStatsResponse, ExecutionTime, Frequency,
+        FundingStatusResponse, FundingTotalsResponse, ListDCAsResponse, ListFundingStatusResponse,
+        NextExecution, NextExecutionsResponse, OperatorEntry, OperatorsResponse, Schedule,
+        SimulateConvertResponse, SourceFunding,
+    },
+    scheduler::scheduler,
+    state::{
+        load_dca, Config, DCAEntry, DCAId, DCAStatus, CONFIG, DCA_HISTORY, DCA_LIST, DCA_STATS,
+        OPERATORS, TASK_USAGE,
+    },
 };
 
-pub fn query_handler(deps: Deps, _env: Env, app: &DCAApp, msg: DCAQueryMsg) -> AppResult<Binary> {
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn query_handler(deps: Deps, env: Env, app: &DCAApp, msg: DCAQueryMsg) -> AppResult<Binary> {
     match msg {
         DCAQueryMsg::Config {} => to_json_binary(&query_config(deps, app)?),
         DCAQueryMsg::DCA { dca_id } => to_json_binary(&query_dca(deps, app, dca_id)?),
+        DCAQueryMsg::ListDCAs {
+            start_after,
+            limit,
+            source_asset,
+            target_asset,
+            dex,
+        } => to_json_binary(&query_list_dcas(
+            deps,
+            start_after,
+            limit,
+            source_asset,
+            target_asset,
+            dex,
+        )?),
+        DCAQueryMsg::DCAStats { dca_id } => to_json_binary(&query_dca_stats(deps, dca_id)?),
+        DCAQueryMsg::DCAHistory {
+            dca_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_dca_history(deps, dca_id, start_after, limit)?),
+        DCAQueryMsg::NextExecutions { dca_id, count } => {
+            to_json_binary(&query_next_executions(deps, &env, dca_id, count)?)
+        }
+        DCAQueryMsg::SimulateConvert { dca_id } => {
+            to_json_binary(&query_simulate_convert(deps, app, dca_id)?)
+        }
+        DCAQueryMsg::SimulateDCA {
+            source_asset,
+            target_asset,
+            dex,
+        } => to_json_binary(&query_simulate(deps, app, source_asset, target_asset, dex)?),
+        DCAQueryMsg::Operators { start_after, limit } => {
+            to_json_binary(&query_operators(deps, start_after, limit)?)
+        }
+        DCAQueryMsg::FundingStatus { dca_id } => {
+            to_json_binary(&query_funding_status(deps, &env, app, dca_id)?)
+        }
+        DCAQueryMsg::ListFundingStatus { start_after, limit } => to_json_binary(
+            &query_list_funding_status(deps, &env, app, start_after, limit)?,
+        ),
+        DCAQueryMsg::FundingTotals {} => to_json_binary(&query_funding_totals(deps, &env, app)?),
     }
     .map_err(Into::into)
 }
@@ -26,6 +90,13 @@ fn query_config(deps: Deps, app: &DCAApp) -> AppResult<ConfigResponse> {
         dca_creation_amount: config.dca_creation_amount,
         refill_threshold: config.refill_threshold,
         max_spread: config.max_spread,
+        keeper_tip: config.keeper_tip,
+        default_schedule: config.default_schedule,
+        fee_rate: config.fee_rate,
+        fee_recipient: config.fee_recipient,
+        gas_limit: config.gas_limit,
+        refill_executions: config.refill_executions,
+        max_refill_amount: config.max_refill_amount,
     })
 }
 
@@ -33,19 +104,483 @@ fn query_config(deps: Deps, app: &DCAApp) -> AppResult<ConfigResponse> {
 fn query_dca(deps: Deps, app: &DCAApp, dca_id: DCAId) -> AppResult<DCAResponse> {
     let dca = DCA_LIST.may_load(deps.storage, dca_id)?;
 
-    let pool_references = if let Some(entry) = dca.as_ref() {
+    let (pool_references, max_spread, gas_limit) = if let Some(entry) = dca.as_ref() {
         let name_service = app.name_service(deps);
+        let config = CONFIG.load(deps.storage)?;
 
-        name_service.query(&DexAssetPairing::new(
+        let pool_references = name_service.query(&DexAssetPairing::new(
             entry.source_asset.name.clone(),
             entry.target_asset.clone(),
             &entry.dex,
-        ))?
+        ))?;
+        (
+            pool_references,
+            Some(entry.max_spread.unwrap_or(config.max_spread)),
+            Some(entry.gas_limit.unwrap_or(config.gas_limit)),
+        )
     } else {
-        vec![]
+        (vec![], None, None)
     };
     Ok(DCAResponse {
         dca,
         pool_references,
+        max_spread,
+        gas_limit,
+    })
+}
+
+/// List dcas, optionally filtered by assets and dex
+fn query_list_dcas(
+    deps: Deps,
+    start_after: Option<DCAId>,
+    limit: Option<u32>,
+    source_asset: Option<AssetEntry>,
+    target_asset: Option<AssetEntry>,
+    dex: Option<DexName>,
+) -> AppResult<ListDCAsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // Use an index for the first filter set, remaining ones are checked per entry
+    let dcas: Box<dyn Iterator<Item = StdResult<(u64, DCAEntry)>> + '_> =
+        if let Some(asset) = &source_asset {
+            Box::new(DCA_LIST.idx.source_asset.prefix(asset.to_string()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            ))
+        } else if let Some(asset) = &target_asset {
+            Box::new(DCA_LIST.idx.target_asset.prefix(asset.to_string()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            ))
+        } else if let Some(dex) = &dex {
+            Box::new(DCA_LIST.idx.dex.prefix(dex.clone()).range(
+                deps.storage,
+                start,
+                None,
+                Order::Ascending,
+            ))
+        } else {
+            Box::new(DCA_LIST.range(deps.storage, start, None, Order::Ascending))
+        };
+
+    let dcas = dcas
+        .filter(|item| match item {
+            Ok((_, dca)) => {
+                (source_asset.is_none() || source_asset.as_ref() == Some(&dca.source_asset.name))
+                    && (target_asset.is_none() || target_asset.as_ref() == Some(&dca.target_asset))
+                    && (dex.is_none() || dex.as_ref() == Some(&dca.dex))
+            }
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|(dca_id, dca)| DCAListEntry {
+                dca_id: DCAId(dca_id),
+                dca,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListDCAsResponse { dcas })
+}
+
+/// Get lifetime statistics of the dca
+fn query_dca_stats(deps: Deps, dca_id: DCAId) -> AppResult<DCAStatsResponse> {
+    load_dca(deps.storage, dca_id)?;
+    let stats = DCA_STATS
+        .may_load(deps.storage, dca_id)?
+        .unwrap_or_default();
+
+    Ok(DCAStatsResponse {
+        source_spent: stats.source_spent,
+        target_received: stats.target_received,
+        average_price: Decimal::checked_from_ratio(stats.source_spent, stats.target_received).ok(),
+        executions: stats.executions,
+        skipped: stats.skipped,
+        failed: stats.failed,
+        fees_paid: stats.fees_paid,
+        first_execution: stats.first_execution,
+        last_execution: stats.last_execution,
+    })
+}
+
+/// Get execution records of the dca
+fn query_dca_history(
+    deps: Deps,
+    dca_id: DCAId,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> AppResult<DCAHistoryResponse> {
+    load_dca(deps.storage, dca_id)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let executions = DCA_HISTORY
+        .prefix(dca_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(execution, record)| DCAHistoryEntry { execution, record }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(DCAHistoryResponse { executions })
+}
+
+/// Get upcoming purchases of the dca
+fn query_next_executions(
+    deps: Deps,
+    env: &Env,
+    dca_id: DCAId,
+    count: u32,
+) -> AppResult<NextExecutionsResponse> {
+    let dca = load_dca(deps.storage, dca_id)?;
+    if dca.status != DCAStatus::Active {
+        return Ok(NextExecutionsResponse { executions: vec![] });
+    }
+    let remaining = dca.max_executions.map_or(u64::MAX, |max_executions| {
+        max_executions.saturating_sub(dca.executions)
+    });
+    let count = (count.min(MAX_LIMIT) as u64).min(remaining) as usize;
+
+    let executions = match &dca.frequency {
+        Frequency::EveryNBlocks(blocks) => {
+            let height = env.block.height;
+            // Heights past u64::MAX never come, so they end the list
+            let first = match (&dca.schedule, dca.last_convert_height) {
+                // CronCat runs block tasks on multiples of the interval
+                (Schedule::CronCat, _) => (height - height % blocks).checked_add(*blocks),
+                (_, Some(last_height)) => last_height
+                    .checked_add(*blocks)
+                    .map(|next_height| next_height.max(height)),
+                (_, None) => Some(height),
+            };
+            let (start, end) = match dca.boundary {
+                Some(DCABoundary::Height { start, end }) => (start, end),
+                _ => (None, None),
+            };
+            let first = first.map(|first| start.map_or(first, |start| first.max(start)));
+            (0..count as u64)
+                .map_while(|i| {
+                    i.checked_mul(*blocks)
+                        .and_then(|offset| first?.checked_add(offset))
+                })
+                .take_while(|height| end.is_none() || Some(*height) <= end)
+                .map(NextExecution::Height)
+                .collect()
+        }
+        Frequency::Cron(expression) => {
+            let schedule = cron_schedule::Schedule::from_str(expression).map_err(|_| {
+                DCAError::InvalidCronExpression {
+                    expression: expression.clone(),
+                }
+            })?;
+            let (start, end) = match dca.boundary {
+                Some(DCABoundary::Time { start, end }) => (start, end),
+                _ => (None, None),
+            };
+            let mut after = env.block.time.nanos();
+            if let Some(start) = start {
+                after = after.max(start.nanos());
+            }
+            let mut executions = vec![];
+            while executions.len() < count {
+                let Ok(next) = schedule.next_after(&after) else {
+                    break;
+                };
+                let time = Timestamp::from_nanos(next);
+                if end.map_or(false, |end| time > end) {
+                    break;
+                }
+                executions.push(NextExecution::Time(time));
+                after = next;
+            }
+            executions
+        }
+        Frequency::Once(ExecutionTime::Height(height)) => {
+            vec![NextExecution::Height(*height)]
+        }
+        Frequency::Once(ExecutionTime::Time(time)) => vec![NextExecution::Time(*time)],
+        Frequency::Immediate => vec![NextExecution::Height(env.block.height + 1)],
+    };
+    let executions = executions.into_iter().take(count).collect();
+
+    Ok(NextExecutionsResponse { executions })
+}
+
+/// Simulate next purchase of the dca
+fn query_simulate_convert(
+    deps: Deps,
+    app: &DCAApp,
+    dca_id: DCAId,
+) -> AppResult<SimulateConvertResponse> {
+    let dca = load_dca(deps.storage, dca_id)?;
+    let source_asset = AnsAsset::new(dca.source_asset.name.clone(), dca.next_amount());
+    query_simulate(deps, app, source_asset, dca.target_asset, dca.dex)
+}
+
+/// Simulate a purchase the same way convert does it, operator fee included
+fn query_simulate(
+    deps: Deps,
+    app: &DCAApp,
+    source_asset: AnsAsset,
+    target_asset: AssetEntry,
+    dex: DexName,
+) -> AppResult<SimulateConvertResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee = source_asset.amount * config.fee_rate;
+    let offer_asset = AnsAsset::new(source_asset.name, source_asset.amount - fee);
+
+    let simulation = app
+        .dex(deps, dex.clone())
+        .simulate_swap(offer_asset.clone(), target_asset.clone())?;
+    let pool_references = app.name_service(deps).query(&DexAssetPairing::new(
+        offer_asset.name.clone(),
+        target_asset,
+        &dex,
+    ))?;
+
+    Ok(SimulateConvertResponse {
+        offer_asset,
+        fee,
+        return_amount: simulation.return_amount,
+        spread_amount: simulation.spread_amount,
+        commission: simulation.commission,
+        pool_references,
+    })
+}
+
+/// List operators with their permissions
+fn query_operators(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> AppResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let operators = OPERATORS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(operator, permissions)| OperatorEntry {
+                operator,
+                permissions,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+/// Get funding of the dca
+fn query_funding_status(
+    deps: Deps,
+    env: &Env,
+    app: &DCAApp,
+    dca_id: DCAId,
+) -> AppResult<FundingStatusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let dca = load_dca(deps.storage, dca_id)?;
+    let native_balance = native_balance(deps, app, &config)?;
+
+    funding_status(deps, env, app, &config, native_balance, dca_id, &dca)
+}
+
+/// List funding of all dcas
+fn query_list_funding_status(
+    deps: Deps,
+    env: &Env,
+    app: &DCAApp,
+    start_after: Option<DCAId>,
+    limit: Option<u32>,
+) -> AppResult<ListFundingStatusResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let config = CONFIG.load(deps.storage)?;
+    let native_balance = native_balance(deps, app, &config)?;
+
+    let statuses = DCA_LIST
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (dca_id, dca) = item?;
+            funding_status(deps, env, app, &config, native_balance, DCAId(dca_id), &dca)
+        })
+        .collect::<AppResult<_>>()?;
+
+    Ok(ListFundingStatusResponse { statuses })
+}
+
+/// Combined funding of all active dcas, against the balances they share
+fn query_funding_totals(deps: Deps, env: &Env, app: &DCAApp) -> AppResult<FundingTotalsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let native_balance = native_balance(deps, app, &config)?;
+
+    let mut task_balance = Uint128::zero();
+    let mut gas_demand = Uint128::zero();
+    let mut source_demand: BTreeMap<String, Uint128> = BTreeMap::new();
+    for item in DCA_LIST.range(deps.storage, None, None, Order::Ascending) {
+        let (dca_id, dca) = item?;
+        if dca.status != DCAStatus::Active {
+            continue;
+        }
+        let dca_id = DCAId(dca_id);
+        task_balance += active_task_balance(deps, env, app, dca_id, &dca)?.unwrap_or_default();
+        gas_demand += match dca.schedule {
+            Schedule::CronCat => TASK_USAGE
+                .may_load(deps.storage, dca_id)?
+                .and_then(|usage| usage.per_execution)
+                .unwrap_or_default(),
+            Schedule::Keeper => config.keeper_tip,
+            #[cfg(feature = "chain-cron")]
+            Schedule::ChainCron => Uint128::zero(),
+        };
+        *source_demand
+            .entry(dca.source_asset.name.to_string())
+            .or_default() += dca.next_amount();
+    }
+
+    // Account refills the tasks unless refills are disabled
+    let refills = if config.refill_threshold.is_zero() {
+        Uint128::zero()
+    } else {
+        native_balance
+    };
+    let gas_runway = (!gas_demand.is_zero()).then(|| runway(task_balance + refills, gas_demand));
+    let bank = app.bank(deps);
+    let sources = source_demand
+        .into_iter()
+        .map(|(asset, demand)| {
+            let asset = AssetEntry::new(&asset);
+            let balance = bank.balance(&asset)?.amount;
+            Ok(SourceFunding {
+                asset,
+                balance,
+                demand,
+                runway: runway(balance, demand),
+            })
+        })
+        .collect::<AppResult<_>>()?;
+
+    Ok(FundingTotalsResponse {
+        native_balance,
+        task_balance,
+        gas_demand,
+        gas_runway,
+        sources,
+    })
+}
+
+/// Balance of the account in [`Config::native_denom`]
+fn native_balance(deps: Deps, app: &DCAApp, config: &Config) -> AppResult<Uint128> {
+    let proxy = app.proxy_address(deps)?;
+    Ok(deps
+        .querier
+        .query_balance(proxy, &config.native_denom)?
+        .amount)
+}
+
+/// Number of executions `funds` cover at `cost` each
+fn runway(funds: Uint128, cost: Uint128) -> u64 {
+    funds.checked_div(cost).map_or(u64::MAX, |runway| {
+        u64::try_from(runway.u128()).unwrap_or(u64::MAX)
+    })
+}
+
+/// Executions left before the budget or the execution limit completes the DCA,
+/// `None` if it has neither
+fn limit_runway(dca: &DCAEntry) -> Option<u64> {
+    let budget_runway = dca.budget.map(|budget| {
+        let left = budget.saturating_sub(dca.spent);
+        // Last purchase spends what's left of the budget
+        let partial = left
+            .checked_rem(dca.source_asset.amount)
+            .map_or(false, |rest| !rest.is_zero());
+        runway(left, dca.source_asset.amount).saturating_add(partial.into())
+    });
+    let executions_runway = dca
+        .max_executions
+        .map(|max_executions| max_executions.saturating_sub(dca.executions));
+    budget_runway.into_iter().chain(executions_runway).min()
+}
+
+/// Task balance of the dca, `None` if it has no task
+fn active_task_balance(
+    deps: Deps,
+    env: &Env,
+    app: &DCAApp,
+    dca_id: DCAId,
+    dca: &DCAEntry,
+) -> AppResult<Option<Uint128>> {
+    // Task of paused or completed DCA is removed
+    if dca.status != DCAStatus::Active {
+        return Ok(None);
+    }
+    match scheduler(deps, app, &dca.schedule).task_balance(env, dca_id) {
+        Ok(task_balance) => Ok(task_balance),
+        Err(DCAError::TaskBalanceMissing {}) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Funding of a single dca, with native balance of the account queried by the caller
+fn funding_status(
+    deps: Deps,
+    env: &Env,
+    app: &DCAApp,
+    config: &Config,
+    native_balance: Uint128,
+    dca_id: DCAId,
+    dca: &DCAEntry,
+) -> AppResult<FundingStatusResponse> {
+    let task_balance = active_task_balance(deps, env, app, dca_id, dca)?;
+    let task_cost_per_execution = TASK_USAGE
+        .may_load(deps.storage, dca_id)?
+        .and_then(|usage| usage.per_execution);
+    let source_balance = app.bank(deps).balance(&dca.source_asset.name)?.amount;
+
+    let gas_runway = match dca.schedule {
+        Schedule::CronCat => match task_balance {
+            None => Some(0),
+            Some(task_balance) => task_cost_per_execution.map(|cost| {
+                // Account refills the task unless refills are disabled
+                let refills = if config.refill_threshold.is_zero() {
+                    Uint128::zero()
+                } else {
+                    native_balance
+                };
+                runway(task_balance + refills, cost)
+            }),
+        },
+        Schedule::Keeper if !config.keeper_tip.is_zero() => {
+            Some(runway(native_balance, config.keeper_tip))
+        }
+        _ => None,
+    };
+    // No need to fund executions past the DCA limits
+    let source_runway =
+        runway(source_balance, dca.next_amount()).min(limit_runway(dca).unwrap_or(u64::MAX));
+    let remaining_executions = if dca.status == DCAStatus::Active {
+        gas_runway.map_or(source_runway, |gas_runway| gas_runway.min(source_runway))
+    } else {
+        0
+    };
+
+    Ok(FundingStatusResponse {
+        dca_id,
+        task_balance,
+        task_cost_per_execution,
+        native_balance,
+        source_balance,
+        gas_runway,
+        source_runway,
+        remaining_executions,
     })
 }
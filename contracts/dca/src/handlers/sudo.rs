@@ -0,0 +1,82 @@
+use abstract_app::abstract_sdk::features::AbstractResponse;
+use cosmwasm_std::{wasm_execute, DepsMut, Env, Order, StdResult, SubMsg};
+use cw_storage_plus::Bound;
+
+use crate::{
+    contract::{AppResult, DCAApp},
+    msg::{DCAExecuteMsg, DCASudoMsg, ExecuteMsg, Schedule},
+    replies::CRON_CONVERT_REPLY_ID,
+    state::{DCAId, CRON_BATCH, CRON_BATCH_SIZE, CRON_CURSOR, DCA_LIST},
+};
+
+pub fn sudo_handler(deps: DepsMut, env: Env, app: DCAApp, msg: DCASudoMsg) -> AppResult {
+    match msg {
+        DCASudoMsg::Cron {} => cron(deps, env, app),
+    }
+}
+
+/// Convert due DCAs with chain cron schedule
+/// Up to [`CRON_BATCH_SIZE`] DCAs are checked per block, round robin from the last checked one
+/// Each DCA is converted by the app calling itself, going through the same checks and swap reply.
+/// Failed convert is recorded in its reply and doesn't revert the others
+fn cron(deps: DepsMut, env: Env, app: DCAApp) -> AppResult {
+    let chain_cron_dcas = DCA_LIST
+        .idx
+        .schedule
+        .prefix(Schedule::ChainCron.name().to_owned());
+    let cursor = CRON_CURSOR.may_load(deps.storage)?;
+
+    let mut checked = chain_cron_dcas
+        .range(
+            deps.storage,
+            cursor.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(CRON_BATCH_SIZE)
+        .collect::<StdResult<Vec<_>>>()?;
+    // Wrap around to the DCAs up to the cursor
+    if let Some(cursor) = cursor.filter(|_| checked.len() < CRON_BATCH_SIZE) {
+        let wrapped = chain_cron_dcas
+            .range(
+                deps.storage,
+                None,
+                Some(Bound::inclusive(cursor)),
+                Order::Ascending,
+            )
+            .take(CRON_BATCH_SIZE - checked.len())
+            .collect::<StdResult<Vec<_>>>()?;
+        checked.extend(wrapped);
+    }
+
+    match checked.last() {
+        Some((dca_id, _)) => CRON_CURSOR.save(deps.storage, &DCAId(*dca_id))?,
+        None => CRON_CURSOR.remove(deps.storage),
+    }
+
+    let due_dcas: Vec<DCAId> = checked
+        .into_iter()
+        .filter(|(_, dca)| dca.is_due(&env.block))
+        .map(|(dca_id, _)| DCAId(dca_id))
+        .collect();
+    if !due_dcas.is_empty() {
+        CRON_BATCH.save(deps.storage, &due_dcas)?;
+    }
+
+    let convert_msgs = due_dcas
+        .iter()
+        .map(|&dca_id| {
+            wasm_execute(
+                env.contract.address.clone(),
+                &ExecuteMsg::from(DCAExecuteMsg::Convert { dca_id }),
+                vec![],
+            )
+            .map(|msg| SubMsg::reply_always(msg, CRON_CONVERT_REPLY_ID))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(app
+        .response("cron")
+        .add_submessages(convert_msgs)
+        .add_attribute("converted", due_dcas.len().to_string()))
+}
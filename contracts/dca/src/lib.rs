@@ -2,6 +2,8 @@ pub mod contract;
 pub mod error;
 mod handlers;
 pub mod msg;
+mod replies;
+mod scheduler;
 pub mod state;
 
 #[cfg(feature = "interface")]
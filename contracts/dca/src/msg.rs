@@ -5,30 +5,17 @@
 
 use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexName, PoolReference};
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Addr, BlockInfo, CheckedFromRatioError, Decimal, Timestamp, Uint128, Uint64};
 use croncat_app::croncat_integration_utils::CronCatInterval;
+use croncat_sdk_tasks::types::{Boundary, BoundaryHeight, BoundaryTime};
 
 use crate::{
     contract::DCAApp,
-    state::{DCAEntry, DCAId},
+    state::{DCAEntry, DCAId, ExecutionRecord},
 };
 
-// QUEST #3.0
-// Replace the following boilerplate with a simple macro call!
-// https://docs.rs/abstract-app/latest/abstract_app/macro.app_msg_types.html
-// {
-
-// These types are your contract API types.
-pub type InstantiateMsg = <DCAApp as abstract_app::abstract_sdk::base::InstantiateEndpoint>::InstantiateMsg;
-pub type ExecuteMsg = <DCAApp as abstract_app::abstract_sdk::base::ExecuteEndpoint>::ExecuteMsg;
-pub type QueryMsg = <DCAApp as abstract_app::abstract_sdk::base::QueryEndpoint>::QueryMsg;
-pub type MigrateMsg = <DCAApp as abstract_app::abstract_sdk::base::MigrateEndpoint>::MigrateMsg;
-
-// These traits indicate that your custom messages are used in the App.
-impl abstract_app::abstract_core::app::AppExecuteMsg for DCAExecuteMsg {}
-impl abstract_app::abstract_core::app::AppQueryMsg for DCAQueryMsg {}
-
-// }
+// This is used for type safety and re-exporting the contract endpoint structs.
+abstract_app::app_msg_types!(DCAApp, DCAExecuteMsg, DCAQueryMsg);
 
 #[cosmwasm_schema::cw_serde]
 #[non_exhaustive]
@@ -38,6 +25,12 @@ pub enum Frequency {
     EveryNBlocks(u64),
     /// Time will schedule the next DCA purchase using crontab.
     Cron(String),
+    /// Single purchase at the given block height or time, the DCA boundary is ignored.
+    /// DCA is completed by the purchase, or expires if the run makes none.
+    Once(ExecutionTime),
+    /// Single purchase as soon as possible.
+    /// DCA is completed by the purchase, or expires if the run makes none.
+    Immediate,
 }
 
 impl Frequency {
@@ -46,9 +39,204 @@ impl Frequency {
         match self {
             Frequency::EveryNBlocks(blocks) => CronCatInterval::Block(blocks),
             Frequency::Cron(cron_tab) => CronCatInterval::Cron(cron_tab),
+            Frequency::Once(_) => CronCatInterval::Once,
+            Frequency::Immediate => CronCatInterval::Immediate,
+        }
+    }
+
+    /// Whether the DCA makes a single purchase
+    pub fn is_one_shot(&self) -> bool {
+        matches!(self, Frequency::Once(_) | Frequency::Immediate)
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Point in time of a single purchase.
+pub enum ExecutionTime {
+    /// At block height
+    Height(u64),
+    /// At block time
+    Time(Timestamp),
+}
+
+impl ExecutionTime {
+    /// Convert `ExecutionTime` to a croncat boundary starting at it
+    pub fn to_boundary(&self) -> Boundary {
+        match *self {
+            ExecutionTime::Height(height) => Boundary::Height(BoundaryHeight {
+                start: Some(height.into()),
+                end: None,
+            }),
+            ExecutionTime::Time(time) => Boundary::Time(BoundaryTime {
+                start: Some(time),
+                end: None,
+            }),
+        }
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Start and end bounds of the DCA schedule.
+pub enum DCABoundary {
+    /// Bounds by block height.
+    Height {
+        /// First block height of purchases
+        start: Option<u64>,
+        /// Last block height of purchases, inclusive like the CronCat boundary
+        end: Option<u64>,
+    },
+    /// Bounds by block time.
+    Time {
+        /// First timestamp of purchases
+        start: Option<Timestamp>,
+        /// Last timestamp of purchases, inclusive like the CronCat boundary
+        end: Option<Timestamp>,
+    },
+}
+
+impl DCABoundary {
+    /// Convert `DCABoundary` to a croncat boundary
+    pub fn to_boundary(self) -> Boundary {
+        match self {
+            DCABoundary::Height { start, end } => Boundary::Height(BoundaryHeight {
+                start: start.map(Uint64::new),
+                end: end.map(Uint64::new),
+            }),
+            DCABoundary::Time { start, end } => Boundary::Time(BoundaryTime { start, end }),
+        }
+    }
+
+    /// Check if the block is within the boundary, both ends included
+    pub fn contains(&self, block: &BlockInfo) -> bool {
+        match self {
+            DCABoundary::Height { start, end } => {
+                !matches!(start, Some(start) if block.height < *start)
+                    && !matches!(end, Some(end) if block.height > *end)
+            }
+            DCABoundary::Time { start, end } => {
+                !matches!(start, Some(start) if block.time < *start)
+                    && !matches!(end, Some(end) if block.time > *end)
+            }
+        }
+    }
+
+    /// Check if the block is past the end of the boundary
+    pub fn has_ended(&self, block: &BlockInfo) -> bool {
+        match self {
+            DCABoundary::Height { end, .. } => matches!(end, Some(end) if block.height > *end),
+            DCABoundary::Time { end, .. } => matches!(end, Some(end) if block.time > *end),
+        }
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+/// Who triggers the DCA purchases.
+pub enum Schedule {
+    /// CronCat task calls [`DCAExecuteMsg::Convert`] on every interval.
+    #[default]
+    CronCat,
+    /// Any address may call [`DCAExecuteMsg::Convert`] once the DCA is due.
+    /// Only block based frequency is supported.
+    Keeper,
+    /// Chain-native cron module calls [`DCASudoMsg::Cron`] every block,
+    /// which converts due DCAs, up to [`crate::state::CRON_BATCH_SIZE`] checked per block.
+    /// Only block based frequency is supported.
+    #[cfg(feature = "chain-cron")]
+    ChainCron,
+}
+
+impl Schedule {
+    /// Name of the schedule, used as its index key
+    pub fn name(&self) -> &'static str {
+        match self {
+            Schedule::CronCat => "cron_cat",
+            Schedule::Keeper => "keeper",
+            #[cfg(feature = "chain-cron")]
+            Schedule::ChainCron => "chain_cron",
+        }
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+/// Actions an operator may take on DCAs of the account.
+pub struct OperatorPermissions {
+    /// Create new DCAs
+    pub create: bool,
+    /// Update existing DCAs
+    pub update: bool,
+    /// Pause and resume DCAs
+    pub pause: bool,
+    /// Cancel DCAs
+    pub cancel: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Price guard checked against a swap simulation before each purchase.
+pub enum PriceLimit {
+    /// Maximum price of the target asset, denominated in the source asset
+    MaxBuyPrice(Decimal),
+    /// Minimum price of the source asset, denominated in the target asset
+    MinSellPrice(Decimal),
+}
+
+impl PriceLimit {
+    /// Check if swapping `offer_amount` for `return_amount` satisfies the limit
+    pub fn is_satisfied(&self, offer_amount: Uint128, return_amount: Uint128) -> bool {
+        match self {
+            PriceLimit::MaxBuyPrice(max_price) => {
+                Decimal::checked_from_ratio(offer_amount, return_amount)
+                    .map_or(false, |price| price <= *max_price)
+            }
+            PriceLimit::MinSellPrice(min_price) => {
+                match Decimal::checked_from_ratio(return_amount, offer_amount) {
+                    Ok(price) => price >= *min_price,
+                    Err(CheckedFromRatioError::Overflow) => true,
+                    Err(CheckedFromRatioError::DivideByZero) => false,
+                }
+            }
         }
     }
 }
+
+#[cosmwasm_schema::cw_serde]
+/// Optional limit of the DCA that can be removed on update.
+pub enum DCALimit {
+    /// Start and end of the DCA schedule
+    Boundary,
+    /// Total amount of source asset to spend
+    Budget,
+    /// Number of conversions to execute
+    MaxExecutions,
+    /// Price guard
+    PriceLimit,
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+/// What happens to the DCA when its swaps fail.
+pub enum FailurePolicy {
+    /// Pause the DCA on the first failed swap
+    #[default]
+    StopOnFail,
+    /// Pause the DCA after this many failed swaps in a row
+    PauseAfter(u32),
+    /// Keep converting no matter how many swaps fail
+    Never,
+}
+
+impl FailurePolicy {
+    /// Whether the DCA should be paused after `consecutive_failures` failed swaps
+    pub fn should_pause(&self, consecutive_failures: u32) -> bool {
+        match self {
+            FailurePolicy::StopOnFail => consecutive_failures > 0,
+            FailurePolicy::PauseAfter(tolerance) => consecutive_failures >= *tolerance,
+            FailurePolicy::Never => false,
+        }
+    }
+}
+
 /// App instantiate message
 #[cosmwasm_schema::cw_serde]
 pub struct AppInstantiateMsg {
@@ -85,6 +273,24 @@ pub enum DCAExecuteMsg {
         task_refill_threshold: Option<Uint128>,
         /// Set the max trade spread
         max_spread: Option<Decimal>,
+        /// Set the tip in native asset paid to keepers for each convert
+        /// TIP: you can set it to "0" to disable tips.
+        keeper_tip: Option<Uint128>,
+        /// Set the scheduler used by new DCAs that don't specify one
+        default_schedule: Option<Schedule>,
+        /// Set the share of each purchase taken as a fee, up to [`crate::state::MAX_FEE_RATE`]
+        fee_rate: Option<Decimal>,
+        /// Set the address receiving the fees
+        fee_recipient: Option<String>,
+        /// Set the gas limit of the convert action for DCAs that don't override it.
+        /// Existing tasks keep their gas limit until they are re-created
+        gas_limit: Option<u64>,
+        /// Set the number of future executions a task refill should pay for,
+        /// based on the task balance consumed by each execution.
+        /// TIP: you can set it to "0" to always refill by `new_dca_task_balance`.
+        refill_executions: Option<u32>,
+        /// Set the cap of a single task refill
+        max_refill_amount: Option<Uint128>,
     },
     /// Used to create a new DCA
     CreateDCA {
@@ -96,6 +302,22 @@ pub enum DCAExecuteMsg {
         frequency: Frequency,
         /// The DEX to be used for the swap
         dex: DexName,
+        /// Optional start and end of the DCA schedule
+        boundary: Option<DCABoundary>,
+        /// Optional total amount of source asset to spend before the DCA completes
+        budget: Option<Uint128>,
+        /// Optional number of conversions to execute before the DCA completes
+        max_executions: Option<u64>,
+        /// Optional max trade spread, overrides the one from config
+        max_spread: Option<Decimal>,
+        /// Optional price guard, purchases at a worse price are skipped
+        price_limit: Option<PriceLimit>,
+        /// Who triggers the purchases, [`ConfigResponse::default_schedule`] if not set
+        schedule: Option<Schedule>,
+        /// What happens when swaps fail, [`FailurePolicy::StopOnFail`] if not set
+        failure_policy: Option<FailurePolicy>,
+        /// Optional gas limit of the convert action, overrides the one from config
+        gas_limit: Option<u64>,
     },
     /// Used to update an existing DCA
     UpdateDCA {
@@ -109,20 +331,68 @@ pub enum DCAExecuteMsg {
         new_frequency: Option<Frequency>,
         /// Optional new DEX to be used for the swap
         new_dex: Option<DexName>,
+        /// Optional new start and end of the DCA schedule
+        new_boundary: Option<DCABoundary>,
+        /// Optional new total amount of source asset to spend
+        new_budget: Option<Uint128>,
+        /// Optional new number of conversions to execute
+        new_max_executions: Option<u64>,
+        /// Optional new max trade spread
+        new_max_spread: Option<Decimal>,
+        /// Optional new price guard
+        new_price_limit: Option<PriceLimit>,
+        /// Optional new failure policy
+        new_failure_policy: Option<FailurePolicy>,
+        /// Optional new gas limit of the convert action
+        new_gas_limit: Option<u64>,
+        /// Optional limits to remove from the DCA, new values of the same limits are applied after
+        clear_limits: Option<Vec<DCALimit>>,
     },
     /// Used to cancel an existing DCA
     CancelDCA {
         /// Unique identifier for the DCA
         dca_id: DCAId,
     },
+    /// Used to pause an existing DCA, keeping its id, settings and history
+    PauseDCA {
+        /// Unique identifier for the DCA
+        dca_id: DCAId,
+    },
+    /// Used to resume a paused DCA
+    ResumeDCA {
+        /// Unique identifier for the DCA
+        dca_id: DCAId,
+    },
+    /// Allow an address to manage DCAs, or change its permissions.
+    /// Only the admin can call this
+    SetOperator {
+        /// Address of the operator
+        operator: String,
+        /// Actions the operator is allowed to take
+        permissions: OperatorPermissions,
+    },
+    /// Revoke all permissions of an operator.
+    /// Only the admin can call this
+    RemoveOperator {
+        /// Address of the operator
+        operator: String,
+    },
     /// Internal method for triggering swap.
-    /// It can be called only by the Croncat Manager
+    /// It can be called only by the Croncat Manager,
+    /// or by anyone once due if the DCA is in [`Schedule::Keeper`] mode
     Convert {
         /// Unique identifier for the DCA
         dca_id: DCAId,
     },
 }
 
+/// DCA sudo messages, sent by the chain
+#[cosmwasm_schema::cw_serde]
+pub enum DCASudoMsg {
+    /// Convert due DCAs with chain cron schedule
+    Cron {},
+}
+
 /// DCA query messages
 #[cosmwasm_schema::cw_serde]
 #[cfg_attr(feature = "interface", derive(cw_orch::QueryFns))]
@@ -140,6 +410,95 @@ pub enum DCAQueryMsg {
         /// Id of the DCA
         dca_id: DCAId,
     },
+    /// List DCA Entries
+    /// Returns [`ListDCAsResponse`]
+    #[returns(ListDCAsResponse)]
+    ListDCAs {
+        /// Id of the DCA to start after
+        start_after: Option<DCAId>,
+        /// Max amount of DCAs to return
+        limit: Option<u32>,
+        /// Only return DCAs purchasing with this asset
+        source_asset: Option<AssetEntry>,
+        /// Only return DCAs purchasing this asset
+        target_asset: Option<AssetEntry>,
+        /// Only return DCAs swapping on this DEX
+        dex: Option<DexName>,
+    },
+    /// Get lifetime statistics of the DCA
+    /// Returns [`DCAStatsResponse`]
+    #[returns(DCAStatsResponse)]
+    DCAStats {
+        /// Id of the DCA
+        dca_id: DCAId,
+    },
+    /// Get executions of the DCA, up to the last [`crate::state::MAX_HISTORY_LENGTH`]
+    /// Returns [`DCAHistoryResponse`]
+    #[returns(DCAHistoryResponse)]
+    DCAHistory {
+        /// Id of the DCA
+        dca_id: DCAId,
+        /// Execution number to start after
+        start_after: Option<u64>,
+        /// Max amount of executions to return
+        limit: Option<u32>,
+    },
+    /// Get upcoming purchases of the DCA, computed from its frequency, schedule and boundary
+    /// Returns [`NextExecutionsResponse`]
+    #[returns(NextExecutionsResponse)]
+    NextExecutions {
+        /// Id of the DCA
+        dca_id: DCAId,
+        /// Max amount of executions to return
+        count: u32,
+    },
+    /// Simulate the next purchase of the DCA
+    /// Returns [`SimulateConvertResponse`]
+    #[returns(SimulateConvertResponse)]
+    SimulateConvert {
+        /// Id of the DCA
+        dca_id: DCAId,
+    },
+    /// Simulate a purchase of a DCA before creating it
+    /// Returns [`SimulateConvertResponse`]
+    #[returns(SimulateConvertResponse)]
+    SimulateDCA {
+        /// The name of the asset to be used for purchasing
+        source_asset: AnsAsset,
+        /// The name of the asset to be purchased
+        target_asset: AssetEntry,
+        /// The name of the dex to be used
+        dex: DexName,
+    },
+    /// List operators and their permissions
+    /// Returns [`OperatorsResponse`]
+    #[returns(OperatorsResponse)]
+    Operators {
+        /// Operator address to start after
+        start_after: Option<String>,
+        /// Max amount of operators to return
+        limit: Option<u32>,
+    },
+    /// Get gas and source funds of the DCA and how many executions they cover
+    /// Returns [`FundingStatusResponse`]
+    #[returns(FundingStatusResponse)]
+    FundingStatus {
+        /// Id of the DCA
+        dca_id: DCAId,
+    },
+    /// List funding status of DCAs, a page at a time.
+    /// Returns [`ListFundingStatusResponse`]
+    #[returns(ListFundingStatusResponse)]
+    ListFundingStatus {
+        /// Id of the DCA to start after
+        start_after: Option<DCAId>,
+        /// Max amount of DCAs to return
+        limit: Option<u32>,
+    },
+    /// Get combined gas and source demand of active DCAs against the account balances
+    /// Returns [`FundingTotalsResponse`]
+    #[returns(FundingTotalsResponse)]
+    FundingTotals {},
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -155,6 +514,20 @@ pub struct ConfigResponse {
     pub refill_threshold: Uint128,
     /// Max trade spread
     pub max_spread: Decimal,
+    /// Tip in native asset paid to keepers for each convert
+    pub keeper_tip: Uint128,
+    /// Scheduler used by new DCAs that don't specify one
+    pub default_schedule: Schedule,
+    /// Share of each purchase taken as a fee, in source asset
+    pub fee_rate: Decimal,
+    /// Address receiving the fees
+    pub fee_recipient: Option<Addr>,
+    /// Gas limit of the convert action for DCAs that don't override it
+    pub gas_limit: u64,
+    /// Number of future executions a task refill pays for, `0` if refills are fixed
+    pub refill_executions: u32,
+    /// Cap of a single task refill, falls back to `dca_creation_amount`
+    pub max_refill_amount: Option<Uint128>,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -164,4 +537,171 @@ pub struct DCAResponse {
     pub dca: Option<DCAEntry>,
     /// Pools used for swapping assets by this DCA task
     pub pool_references: Vec<PoolReference>,
+    /// Max trade spread used by this DCA task, falls back to the config one
+    pub max_spread: Option<Decimal>,
+    /// Gas limit of the convert action of this DCA task, falls back to the config one
+    pub gas_limit: Option<u64>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for simulate_convert and simulate_d_c_a queries
+pub struct SimulateConvertResponse {
+    /// Source asset offered to the dex, after the operator fee
+    pub offer_asset: AnsAsset,
+    /// Operator fee taken from the source amount
+    pub fee: Uint128,
+    /// Expected amount of the target asset
+    pub return_amount: Uint128,
+    /// Spread of the swap, in the target asset
+    pub spread_amount: Uint128,
+    /// Commission charged by the pool
+    pub commission: (AssetEntry, Uint128),
+    /// Pools used for the swap
+    pub pool_references: Vec<PoolReference>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// DCA entry with its id
+pub struct DCAListEntry {
+    /// Id of the DCA
+    pub dca_id: DCAId,
+    /// DCA entry
+    pub dca: DCAEntry,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for list_d_c_as query
+pub struct ListDCAsResponse {
+    /// DCA entries ordered by id
+    pub dcas: Vec<DCAListEntry>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Execution record with its number
+pub struct DCAHistoryEntry {
+    /// Number of the execution, starting from 1
+    pub execution: u64,
+    /// Execution record
+    pub record: ExecutionRecord,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for d_c_a_history query
+pub struct DCAHistoryResponse {
+    /// Execution records ordered by execution number
+    pub executions: Vec<DCAHistoryEntry>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Upcoming purchase of the DCA
+pub enum NextExecution {
+    /// Block height of the purchase, for block frequency
+    Height(u64),
+    /// Time of the purchase, for cron frequency
+    Time(Timestamp),
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for next_executions query
+pub struct NextExecutionsResponse {
+    /// Upcoming purchases in order, empty if the DCA is not active
+    pub executions: Vec<NextExecution>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Operator with its permissions
+pub struct OperatorEntry {
+    /// Address of the operator
+    pub operator: Addr,
+    /// Actions the operator is allowed to take
+    pub permissions: OperatorPermissions,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for operators query
+pub struct OperatorsResponse {
+    /// Operators ordered by address
+    pub operators: Vec<OperatorEntry>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for funding_status query
+pub struct FundingStatusResponse {
+    /// Id of the DCA
+    pub dca_id: DCAId,
+    /// Balance of the CronCat task in native asset, `None` if the DCA has no task
+    pub task_balance: Option<Uint128>,
+    /// Task balance consumed by each execution, `None` until observed by convert
+    pub task_cost_per_execution: Option<Uint128>,
+    /// Balance of the account in native asset, used for task refills and keeper tips
+    pub native_balance: Uint128,
+    /// Balance of the account in source asset
+    pub source_balance: Uint128,
+    /// Executions gas funds cover, `None` if unknown or not limited by gas
+    pub gas_runway: Option<u64>,
+    /// Executions the source balance covers for this DCA alone,
+    /// up to the executions left by the budget and execution limit
+    pub source_runway: u64,
+    /// Estimated executions before either gas or source funds run out
+    /// or the DCA completes, zero if the DCA is not active
+    pub remaining_executions: u64,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for list_funding_status query
+pub struct ListFundingStatusResponse {
+    /// Funding status of DCAs ordered by id
+    pub statuses: Vec<FundingStatusResponse>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for funding_totals query
+pub struct FundingTotalsResponse {
+    /// Balance of the account in native asset, used for task refills and keeper tips
+    pub native_balance: Uint128,
+    /// Sum of CronCat task balances of active DCAs
+    pub task_balance: Uint128,
+    /// Native asset spent by one execution of every active DCA,
+    /// only counting observed task costs and keeper tips
+    pub gas_demand: Uint128,
+    /// Rounds of executions of all active DCAs gas funds cover, `None` if there is no gas demand
+    pub gas_runway: Option<u64>,
+    /// Demand on each source asset of active DCAs
+    pub sources: Vec<SourceFunding>,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Combined demand of active DCAs on one source asset
+pub struct SourceFunding {
+    /// Source asset
+    pub asset: AssetEntry,
+    /// Balance of the account in source asset
+    pub balance: Uint128,
+    /// Source asset spent by the next execution of every DCA converting it
+    pub demand: Uint128,
+    /// Rounds of executions of these DCAs the balance covers
+    pub runway: u64,
+}
+
+#[cosmwasm_schema::cw_serde]
+/// Response for d_c_a_stats query
+pub struct DCAStatsResponse {
+    /// Total amount of source asset spent
+    pub source_spent: Uint128,
+    /// Total amount of target asset acquired
+    pub target_received: Uint128,
+    /// Average price of the target asset, denominated in the source asset
+    pub average_price: Option<Decimal>,
+    /// Number of executed swaps
+    pub executions: u64,
+    /// Number of executions skipped because of the price limit
+    pub skipped: u64,
+    /// Number of executions where the swap failed
+    pub failed: u64,
+    /// Total amount of source asset paid as fees
+    pub fees_paid: Uint128,
+    /// Time of the first swap
+    pub first_execution: Option<Timestamp>,
+    /// Time of the last swap
+    pub last_execution: Option<Timestamp>,
 }
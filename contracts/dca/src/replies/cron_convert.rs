@@ -0,0 +1,38 @@
+use abstract_app::abstract_sdk::features::AbstractResponse;
+use cosmwasm_std::{DepsMut, Env, Reply, SubMsgResult};
+
+use crate::{
+    contract::{AppResult, DCAApp},
+    state::{CRON_BATCH, DCA_LIST, DCA_STATS},
+};
+
+/// Record a failed convert of a chain cron DCA,
+/// so it doesn't revert the converts of other DCAs in the same block
+pub fn cron_convert_reply(deps: DepsMut, _env: Env, app: DCAApp, reply: Reply) -> AppResult {
+    // Replies come in the order the converts were sent
+    let mut batch = CRON_BATCH.load(deps.storage)?;
+    let dca_id = batch.remove(0);
+    if batch.is_empty() {
+        CRON_BATCH.remove(deps.storage);
+    } else {
+        CRON_BATCH.save(deps.storage, &batch)?;
+    }
+
+    let response = app
+        .response("cron_convert_reply")
+        .add_attribute("dca_id", dca_id);
+    match reply.result {
+        SubMsgResult::Ok(_) => Ok(response),
+        SubMsgResult::Err(error) => {
+            // DCA may be gone if convert failed to load it
+            if DCA_LIST.may_load(deps.storage, dca_id)?.is_some() {
+                let mut stats = DCA_STATS
+                    .may_load(deps.storage, dca_id)?
+                    .unwrap_or_default();
+                stats.failed += 1;
+                DCA_STATS.save(deps.storage, dca_id, &stats)?;
+            }
+            Ok(response.add_attribute("convert_error", error))
+        }
+    }
+}
@@ -0,0 +1,11 @@
+#[cfg(feature = "chain-cron")]
+mod cron_convert;
+mod swap;
+
+#[cfg(feature = "chain-cron")]
+pub use cron_convert::cron_convert_reply;
+pub use swap::swap_reply;
+
+pub const SWAP_REPLY_ID: u64 = 1u64;
+#[cfg(feature = "chain-cron")]
+pub const CRON_CONVERT_REPLY_ID: u64 = 2u64;
@@ -0,0 +1,127 @@
+use abstract_app::abstract_core::objects::AnsAsset;
+use abstract_app::abstract_sdk::{features::AbstractResponse, Execution, TransferInterface};
+use cosmwasm_std::{Decimal, DepsMut, Env, Reply, SubMsgResult};
+
+use crate::{
+    contract::{AppResult, DCAApp},
+    scheduler::scheduler,
+    state::{
+        load_dca, DCAStatus, ExecutionRecord, CONFIG, DCA_HISTORY, DCA_LIST, DCA_STATS,
+        MAX_HISTORY_LENGTH, PENDING_CONVERT,
+    },
+};
+
+/// Record actual amounts of the swap done by convert,
+/// or apply the failure policy of the DCA if the swap failed
+pub fn swap_reply(deps: DepsMut, env: Env, app: DCAApp, reply: Reply) -> AppResult {
+    let pending = PENDING_CONVERT.load(deps.storage)?;
+    PENDING_CONVERT.remove(deps.storage);
+    let mut dca = load_dca(deps.storage, pending.dca_id)?;
+
+    let mut stats = DCA_STATS
+        .may_load(deps.storage, pending.dca_id)?
+        .unwrap_or_default();
+
+    // Refills and bookkeeping of the convert are kept, only the swap is reverted
+    if let SubMsgResult::Err(error) = reply.result {
+        stats.failed += 1;
+        DCA_STATS.save(deps.storage, pending.dca_id, &stats)?;
+        // One-shot DCA won't run again, its task is removed by CronCat
+        let expired = dca.frequency.is_one_shot();
+        if expired {
+            dca.status = DCAStatus::Expired;
+        }
+        let paused = dca.record_failure();
+        let response = app
+            .response("convert_reply")
+            .add_attribute("dca_id", pending.dca_id)
+            .add_attribute("swap_error", error)
+            .add_attribute("consecutive_failures", dca.consecutive_failures.to_string());
+        let response = if expired {
+            response.add_attribute("dca_status", "expired")
+        } else if paused {
+            let task_msgs =
+                scheduler(deps.as_ref(), &app, &dca.schedule).remove_task(pending.dca_id)?;
+            response
+                .add_messages(task_msgs)
+                .add_attribute("dca_status", "paused")
+        } else {
+            response
+        };
+        DCA_LIST.save(deps.storage, pending.dca_id, &dca)?;
+        return Ok(response);
+    }
+
+    let bank = app.bank(deps.as_ref());
+    let source_balance = bank.balance(&dca.source_asset.name)?.amount;
+    let target_balance = bank.balance(&dca.target_asset)?.amount;
+    let source_spent = pending.source_balance.saturating_sub(source_balance);
+    let target_received = target_balance.saturating_sub(pending.target_balance);
+
+    dca.spent += pending.amount;
+    dca.executions += 1;
+    dca.consecutive_failures = 0;
+
+    let record = ExecutionRecord {
+        block_height: env.block.height,
+        time: env.block.time,
+        source_spent,
+        target_received,
+        price: Decimal::checked_from_ratio(source_spent, target_received).unwrap_or_default(),
+        dex: dca.dex.clone(),
+    };
+    let execution = dca.executions;
+    DCA_HISTORY.save(deps.storage, (pending.dca_id, execution), &record)?;
+    // Keep history bounded
+    if execution > MAX_HISTORY_LENGTH {
+        DCA_HISTORY.remove(
+            deps.storage,
+            (pending.dca_id, execution - MAX_HISTORY_LENGTH),
+        );
+    }
+
+    stats.source_spent += source_spent;
+    stats.target_received += target_received;
+    stats.executions += 1;
+    stats.first_execution.get_or_insert(env.block.time);
+    stats.last_execution = Some(env.block.time);
+
+    let response = app
+        .response("convert_reply")
+        .add_attribute("dca_id", pending.dca_id)
+        .add_attribute("source_spent", source_spent)
+        .add_attribute("target_received", target_received);
+    // Fee is charged only for successful swaps
+    let config = CONFIG.load(deps.storage)?;
+    let response = match &config.fee_recipient {
+        Some(fee_recipient) if !pending.fee.is_zero() => {
+            let fee_transfer = app.bank(deps.as_ref()).transfer(
+                vec![AnsAsset::new(dca.source_asset.name.clone(), pending.fee)],
+                fee_recipient,
+            )?;
+            stats.fees_paid += pending.fee;
+            response
+                .add_message(app.executor(deps.as_ref()).execute(vec![fee_transfer])?)
+                .add_attribute("fee", pending.fee)
+        }
+        _ => response,
+    };
+    DCA_STATS.save(deps.storage, pending.dca_id, &stats)?;
+    // One-shot DCA is completed by its only purchase, its task is removed by CronCat
+    let response = if dca.status == DCAStatus::Active && dca.frequency.is_one_shot() {
+        dca.status = DCAStatus::Completed;
+        response.add_attribute("dca_status", "completed")
+    } else if dca.status == DCAStatus::Active && dca.limit_reached() {
+        dca.status = DCAStatus::Completed;
+        let task_msgs =
+            scheduler(deps.as_ref(), &app, &dca.schedule).remove_task(pending.dca_id)?;
+        response
+            .add_messages(task_msgs)
+            .add_attribute("dca_status", "completed")
+    } else {
+        response
+    };
+    DCA_LIST.save(deps.storage, pending.dca_id, &dca)?;
+
+    Ok(response)
+}
@@ -0,0 +1,59 @@
+use cosmwasm_std::{Addr, CosmosMsg, Env, Uint128};
+
+use super::Scheduler;
+use crate::{
+    contract::AppResult,
+    error::DCAError,
+    state::{Config, DCAEntry, DCAId},
+};
+
+/// Chain-native cron backend. The chain calls
+/// [`DCASudoMsg::Cron`](crate::msg::DCASudoMsg::Cron) which makes the app
+/// convert its due DCAs, so there are no tasks to create or fund.
+pub struct ChainCronScheduler;
+
+impl Scheduler for ChainCronScheduler {
+    fn create_task(
+        &self,
+        _env: &Env,
+        _dca_id: DCAId,
+        _dca: &DCAEntry,
+        _config: &Config,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn remove_task(&self, _dca_id: DCAId) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn refill_task(
+        &self,
+        _dca_id: DCAId,
+        _config: &Config,
+        _amount: Uint128,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn task_balance(&self, _env: &Env, _dca_id: DCAId) -> AppResult<Option<Uint128>> {
+        Ok(None)
+    }
+
+    fn assert_caller(
+        &self,
+        env: &Env,
+        _dca_id: DCAId,
+        dca: &DCAEntry,
+        sender: &Addr,
+    ) -> AppResult<()> {
+        // Sudo handler converts by calling the app itself
+        if sender != env.contract.address {
+            return Err(DCAError::NotChainCronConvert {});
+        }
+        if !dca.is_due(&env.block) {
+            return Err(DCAError::NotDue {});
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use cosmwasm_std::{wasm_execute, Addr, CosmosMsg, Env, Uint128};
+use croncat_app::{
+    croncat_integration_utils::{CronCatAction, CronCatTaskRequest},
+    CronCat,
+};
+use cw_asset::{Asset, AssetList};
+
+use super::Scheduler;
+use crate::{
+    contract::{AppResult, DCAApp},
+    error::DCAError,
+    msg::{DCABoundary, DCAExecuteMsg, ExecuteMsg, Frequency},
+    state::{Config, DCAEntry, DCAId},
+};
+
+/// Default backend, every DCA gets its own CronCat task
+/// funded from the account in [`Config::native_denom`]
+pub struct CronCatScheduler<'a> {
+    cron_cat: CronCat<'a, DCAApp>,
+}
+
+impl<'a> CronCatScheduler<'a> {
+    pub fn new(cron_cat: CronCat<'a, DCAApp>) -> Self {
+        Self { cron_cat }
+    }
+}
+
+impl<'a> Scheduler for CronCatScheduler<'a> {
+    fn create_task(
+        &self,
+        env: &Env,
+        dca_id: DCAId,
+        dca: &DCAEntry,
+        config: &Config,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        let interval = dca.frequency.to_interval();
+        let boundary = match &dca.frequency {
+            Frequency::Once(at) => Some(at.to_boundary()),
+            _ => dca.boundary.clone().map(DCABoundary::to_boundary),
+        };
+        let task = CronCatTaskRequest {
+            interval,
+            boundary,
+            // Failure policy is applied by the app, a failed convert
+            // (e.g. insufficient balance) shouldn't remove the task behind its back
+            stop_on_fail: false,
+            actions: vec![CronCatAction {
+                msg: wasm_execute(
+                    env.contract.address.clone(),
+                    &ExecuteMsg::from(DCAExecuteMsg::Convert { dca_id }),
+                    vec![],
+                )?
+                .into(),
+                gas_limit: Some(dca.gas_limit.unwrap_or(config.gas_limit)),
+            }],
+            queries: None,
+            transforms: None,
+            cw20: None,
+        };
+        let assets = AssetList::from(vec![Asset::native(
+            config.native_denom.clone(),
+            config.dca_creation_amount,
+        )])
+        .into();
+        Ok(vec![self.cron_cat.create_task(task, dca_id, assets)?])
+    }
+
+    fn remove_task(&self, dca_id: DCAId) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![self.cron_cat.remove_task(dca_id)?])
+    }
+
+    fn refill_task(
+        &self,
+        dca_id: DCAId,
+        config: &Config,
+        amount: Uint128,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        let assets =
+            AssetList::from(vec![Asset::native(config.native_denom.clone(), amount)]).into();
+        Ok(vec![self.cron_cat.refill_task(dca_id, assets)?])
+    }
+
+    fn task_balance(&self, env: &Env, dca_id: DCAId) -> AppResult<Option<Uint128>> {
+        let task_balance = self
+            .cron_cat
+            .query_task_balance(env.contract.address.clone(), dca_id)?
+            .balance
+            .ok_or(DCAError::TaskBalanceMissing {})?;
+        Ok(Some(task_balance.native_balance))
+    }
+
+    fn assert_caller(
+        &self,
+        env: &Env,
+        dca_id: DCAId,
+        _dca: &DCAEntry,
+        sender: &Addr,
+    ) -> AppResult<()> {
+        let manager_addr = self
+            .cron_cat
+            .query_manager_addr(env.contract.address.clone(), dca_id)?;
+        if manager_addr != sender {
+            return Err(DCAError::NotManagerConvert {});
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use cosmwasm_std::{Addr, CosmosMsg, Env, Uint128};
+
+use super::Scheduler;
+use crate::{
+    contract::AppResult,
+    error::DCAError,
+    state::{Config, DCAEntry, DCAId},
+};
+
+/// Permissionless backend, anyone may convert once the DCA is due.
+/// Nothing to create or fund.
+pub struct KeeperScheduler;
+
+impl Scheduler for KeeperScheduler {
+    fn create_task(
+        &self,
+        _env: &Env,
+        _dca_id: DCAId,
+        _dca: &DCAEntry,
+        _config: &Config,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn remove_task(&self, _dca_id: DCAId) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn refill_task(
+        &self,
+        _dca_id: DCAId,
+        _config: &Config,
+        _amount: Uint128,
+    ) -> AppResult<Vec<CosmosMsg>> {
+        Ok(vec![])
+    }
+
+    fn task_balance(&self, _env: &Env, _dca_id: DCAId) -> AppResult<Option<Uint128>> {
+        Ok(None)
+    }
+
+    fn assert_caller(
+        &self,
+        env: &Env,
+        _dca_id: DCAId,
+        dca: &DCAEntry,
+        _sender: &Addr,
+    ) -> AppResult<()> {
+        if !dca.is_due(&env.block) {
+            return Err(DCAError::NotDue {});
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+//! Backends that trigger [`DCAExecuteMsg::Convert`](crate::msg::DCAExecuteMsg::Convert)
+//! of a DCA on its schedule.
+
+#[cfg(feature = "chain-cron")]
+mod chain_cron;
+mod croncat;
+mod keeper;
+
+#[cfg(feature = "chain-cron")]
+pub use chain_cron::ChainCronScheduler;
+pub use croncat::CronCatScheduler;
+pub use keeper::KeeperScheduler;
+
+use cosmwasm_std::{Addr, CosmosMsg, Deps, Env, Uint128};
+use croncat_app::CronCatInterface;
+
+use crate::{
+    contract::{AppResult, DCAApp},
+    msg::Schedule,
+    state::{Config, DCAEntry, DCAId},
+};
+
+pub trait Scheduler {
+    /// Messages to start triggering convert of the DCA
+    fn create_task(
+        &self,
+        env: &Env,
+        dca_id: DCAId,
+        dca: &DCAEntry,
+        config: &Config,
+    ) -> AppResult<Vec<CosmosMsg>>;
+
+    /// Messages to stop triggering convert of the DCA
+    fn remove_task(&self, dca_id: DCAId) -> AppResult<Vec<CosmosMsg>>;
+
+    /// Messages to top up the task balance by `amount` of [`Config::native_denom`]
+    fn refill_task(
+        &self,
+        dca_id: DCAId,
+        config: &Config,
+        amount: Uint128,
+    ) -> AppResult<Vec<CosmosMsg>>;
+
+    /// Remaining task balance in [`Config::native_denom`],
+    /// `None` if the backend does not need one.
+    /// Fails with [`DCAError::TaskBalanceMissing`](crate::error::DCAError::TaskBalanceMissing)
+    /// if the task is gone
+    fn task_balance(&self, env: &Env, dca_id: DCAId) -> AppResult<Option<Uint128>>;
+
+    /// Check that `sender` is allowed to convert the DCA now
+    fn assert_caller(
+        &self,
+        env: &Env,
+        dca_id: DCAId,
+        dca: &DCAEntry,
+        sender: &Addr,
+    ) -> AppResult<()>;
+}
+
+/// Scheduler backend of the DCA
+pub fn scheduler<'a>(
+    deps: Deps<'a>,
+    app: &'a DCAApp,
+    schedule: &Schedule,
+) -> Box<dyn Scheduler + 'a> {
+    match schedule {
+        Schedule::CronCat => Box::new(CronCatScheduler::new(app.cron_cat(deps))),
+        Schedule::Keeper => Box::new(KeeperScheduler),
+        #[cfg(feature = "chain-cron")]
+        Schedule::ChainCron => Box::new(ChainCronScheduler),
+    }
+}
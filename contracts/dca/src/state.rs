@@ -1,8 +1,13 @@
 use abstract_app::abstract_core::objects::{AnsAsset, AssetEntry, DexName};
-use cosmwasm_std::{Decimal, Uint128};
-use cw_storage_plus::{Item, Key, KeyDeserialize, Map, PrimaryKey};
+use cosmwasm_std::{Addr, BlockInfo, Decimal, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Key, KeyDeserialize, Map, MultiIndex, Prefixer, PrimaryKey,
+};
 
-use crate::msg::Frequency;
+use crate::error::DCAError;
+use crate::msg::{
+    DCABoundary, FailurePolicy, Frequency, OperatorPermissions, PriceLimit, Schedule,
+};
 
 #[cosmwasm_schema::cw_serde]
 pub struct Config {
@@ -10,6 +15,40 @@ pub struct Config {
     pub dca_creation_amount: Uint128,
     pub refill_threshold: Uint128,
     pub max_spread: Decimal,
+    #[serde(default)]
+    pub keeper_tip: Uint128,
+    #[serde(default)]
+    pub default_schedule: Schedule,
+    #[serde(default)]
+    pub fee_rate: Decimal,
+    #[serde(default)]
+    pub fee_recipient: Option<Addr>,
+    /// Gas limit of the convert action for DCAs that don't override it
+    #[serde(default = "default_gas_limit")]
+    pub gas_limit: u64,
+    /// Number of future executions a refill should pay for,
+    /// `0` to always refill by `dca_creation_amount`
+    #[serde(default)]
+    pub refill_executions: u32,
+    /// Cap of a single refill, `dca_creation_amount` if not set
+    #[serde(default)]
+    pub max_refill_amount: Option<Uint128>,
+}
+
+impl Config {
+    /// Amount to refill a task that consumes `per_execution` on each run
+    pub fn refill_amount(&self, per_execution: Option<Uint128>) -> Uint128 {
+        match per_execution {
+            Some(per_execution) if self.refill_executions > 0 => per_execution
+                .saturating_mul(Uint128::from(self.refill_executions))
+                .min(self.max_refill_amount.unwrap_or(self.dca_creation_amount)),
+            _ => self.dca_creation_amount,
+        }
+    }
+}
+
+fn default_gas_limit() -> u64 {
+    DEFAULT_GAS_LIMIT
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -18,6 +57,106 @@ pub struct DCAEntry {
     pub target_asset: AssetEntry,
     pub frequency: Frequency,
     pub dex: DexName,
+    /// Start and end of the schedule
+    pub boundary: Option<DCABoundary>,
+    /// Total amount of source asset this DCA is allowed to spend
+    pub budget: Option<Uint128>,
+    /// Maximum number of conversions this DCA is allowed to execute
+    pub max_executions: Option<u64>,
+    /// Overrides max trade spread from the config
+    pub max_spread: Option<Decimal>,
+    /// Purchases are skipped when the simulated price is worse than this limit
+    pub price_limit: Option<PriceLimit>,
+    /// Amount of source asset spent so far
+    pub spent: Uint128,
+    /// Number of conversions executed so far
+    pub executions: u64,
+    pub status: DCAStatus,
+    pub schedule: Schedule,
+    /// Block height of the last convert, including skipped ones
+    pub last_convert_height: Option<u64>,
+    /// Block time of the last convert, including skipped ones
+    pub last_convert_time: Option<Timestamp>,
+    pub failure_policy: FailurePolicy,
+    /// Number of failed swaps since the last successful one
+    pub consecutive_failures: u32,
+    /// Overrides gas limit of the convert action from the config
+    pub gas_limit: Option<u64>,
+}
+
+impl DCAEntry {
+    /// Amount of source asset to convert on the next execution,
+    /// capped by what's left of the budget
+    pub fn next_amount(&self) -> Uint128 {
+        match self.budget {
+            Some(budget) => self
+                .source_asset
+                .amount
+                .min(budget.saturating_sub(self.spent)),
+            None => self.source_asset.amount,
+        }
+    }
+
+    /// Whether the DCA can be converted by a keeper at this block
+    pub fn is_due(&self, block: &BlockInfo) -> bool {
+        if self.status != DCAStatus::Active {
+            return false;
+        }
+        if let Some(boundary) = &self.boundary {
+            if !boundary.contains(block) {
+                return false;
+            }
+        }
+        match (&self.frequency, self.last_convert_height) {
+            (_, None) => true,
+            (Frequency::EveryNBlocks(blocks), Some(last_height)) => {
+                block.height >= last_height.saturating_add(*blocks)
+            }
+            (_, Some(_)) => false,
+        }
+    }
+
+    /// Whether purchasing `amount` more would exhaust the budget or the execution limit
+    pub fn completes_with(&self, amount: Uint128) -> bool {
+        self.budget
+            .map_or(false, |budget| self.spent + amount >= budget)
+            || self.max_executions.map_or(false, |max_executions| {
+                self.executions + 1 >= max_executions
+            })
+    }
+
+    /// Count a failed swap, pausing the active DCA if its failure policy says so.
+    /// Returns whether the DCA got paused
+    pub fn record_failure(&mut self) -> bool {
+        self.consecutive_failures += 1;
+        let pause = self.status == DCAStatus::Active
+            && self.failure_policy.should_pause(self.consecutive_failures);
+        if pause {
+            self.status = DCAStatus::Paused;
+        }
+        pause
+    }
+
+    /// Whether the budget or the execution limit of this DCA is exhausted
+    pub fn limit_reached(&self) -> bool {
+        self.budget.map_or(false, |budget| self.spent >= budget)
+            || self
+                .max_executions
+                .map_or(false, |max_executions| self.executions >= max_executions)
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum DCAStatus {
+    /// DCA is scheduled and converting
+    Active,
+    /// DCA is paused by the admin, task is removed
+    Paused,
+    /// DCA reached its budget or execution limit, or made its single purchase, task is removed
+    Completed,
+    /// Single purchase DCA ran without purchasing, task is removed.
+    /// It can be retried by updating it
+    Expired,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -44,6 +183,12 @@ impl<'a> PrimaryKey<'a> for DCAId {
     }
 }
 
+impl<'a> Prefixer<'a> for DCAId {
+    fn prefix(&self) -> Vec<Key> {
+        self.0.prefix()
+    }
+}
+
 impl KeyDeserialize for DCAId {
     type Output = u64;
 
@@ -59,6 +204,160 @@ impl From<DCAId> for String {
     }
 }
 
+#[cosmwasm_schema::cw_serde]
+pub struct ExecutionRecord {
+    pub block_height: u64,
+    pub time: Timestamp,
+    /// Amount of source asset that left the account
+    pub source_spent: Uint128,
+    /// Amount of target asset that arrived to the account
+    pub target_received: Uint128,
+    /// Price of the target asset, denominated in the source asset
+    pub price: Decimal,
+    pub dex: DexName,
+}
+
+/// Lifetime totals of the DCA, unlike history these are never truncated
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct ExecutionStats {
+    pub source_spent: Uint128,
+    pub target_received: Uint128,
+    pub executions: u64,
+    /// Executions skipped because of the price limit
+    pub skipped: u64,
+    /// Executions where the swap failed
+    pub failed: u64,
+    /// Source asset paid as fees
+    #[serde(default)]
+    pub fees_paid: Uint128,
+    pub first_execution: Option<Timestamp>,
+    pub last_execution: Option<Timestamp>,
+}
+
+/// Account balances of the DCA assets before the swap,
+/// compared in the swap reply to get actual amounts
+#[cosmwasm_schema::cw_serde]
+pub struct PendingConvert {
+    pub dca_id: DCAId,
+    /// Source amount of this purchase, including the fee
+    pub amount: Uint128,
+    /// Fee sent to the fee recipient once the swap succeeds
+    pub fee: Uint128,
+    pub source_balance: Uint128,
+    pub target_balance: Uint128,
+}
+
+/// Task balance observed on convert, used to size refills
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct TaskUsage {
+    /// Task balance after the last convert, including its refill.
+    /// `None` until the first convert of a new task
+    pub last_balance: Option<Uint128>,
+    /// Task balance consumed between the last two converts
+    pub per_execution: Option<Uint128>,
+}
+
+impl TaskUsage {
+    /// Update consumption with the task balance seen by convert
+    pub fn observe(&mut self, balance: Uint128) {
+        let consumed = self
+            .last_balance
+            .and_then(|last_balance| last_balance.checked_sub(balance).ok());
+        if let Some(consumed) = consumed.filter(|consumed| !consumed.is_zero()) {
+            self.per_execution = Some(consumed);
+        }
+        self.last_balance = Some(balance);
+    }
+}
+
+/// Forget the task balance seen by convert, as the task gets re-created
+pub fn reset_task_usage(storage: &mut dyn Storage, dca_id: DCAId) -> StdResult<()> {
+    if let Some(mut usage) = TASK_USAGE.may_load(storage, dca_id)? {
+        usage.last_balance = None;
+        TASK_USAGE.save(storage, dca_id, &usage)?;
+    }
+    Ok(())
+}
+
+/// Max amount of execution records kept per DCA
+pub const MAX_HISTORY_LENGTH: u64 = 100;
+
+/// Load DCA entry, failing with [`DCAError::DCANotFound`] if there is none
+pub fn load_dca(storage: &dyn Storage, dca_id: DCAId) -> Result<DCAEntry, DCAError> {
+    DCA_LIST
+        .may_load(storage, dca_id)?
+        .ok_or(DCAError::DCANotFound { dca_id })
+}
+
+/// Max share of each purchase that can be taken as a fee
+pub const MAX_FEE_RATE: Decimal = Decimal::percent(10);
+
+/// Gas limit of the convert action used before it was configurable
+pub const DEFAULT_GAS_LIMIT: u64 = 300_000;
+/// Bounds of the convert action gas limit
+pub const MIN_GAS_LIMIT: u64 = 100_000;
+pub const MAX_GAS_LIMIT: u64 = 3_000_000;
+
+/// Max amount of chain cron DCAs checked per block
+pub const CRON_BATCH_SIZE: usize = 10;
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const NEXT_ID: Item<DCAId> = Item::new("next_id");
-pub const DCA_LIST: Map<DCAId, DCAEntry> = Map::new("dca_list");
+pub const DCA_LIST: IndexedMap<DCAId, DCAEntry, DCAIndexes> = IndexedMap::new(
+    "dca_list",
+    DCAIndexes {
+        source_asset: MultiIndex::new(source_asset_idx, "dca_list", "dca_list__source_asset"),
+        target_asset: MultiIndex::new(target_asset_idx, "dca_list", "dca_list__target_asset"),
+        dex: MultiIndex::new(dex_idx, "dca_list", "dca_list__dex"),
+        schedule: MultiIndex::new(schedule_idx, "dca_list", "dca_list__schedule"),
+    },
+);
+
+/// Secondary indexes of the [`DCA_LIST`]
+pub struct DCAIndexes<'a> {
+    pub source_asset: MultiIndex<'a, String, DCAEntry, DCAId>,
+    pub target_asset: MultiIndex<'a, String, DCAEntry, DCAId>,
+    pub dex: MultiIndex<'a, String, DCAEntry, DCAId>,
+    pub schedule: MultiIndex<'a, String, DCAEntry, DCAId>,
+}
+
+impl<'a> IndexList<DCAEntry> for DCAIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DCAEntry>> + '_> {
+        let v: Vec<&dyn Index<DCAEntry>> = vec![
+            &self.source_asset,
+            &self.target_asset,
+            &self.dex,
+            &self.schedule,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+fn source_asset_idx(_pk: &[u8], dca: &DCAEntry) -> String {
+    dca.source_asset.name.to_string()
+}
+
+fn target_asset_idx(_pk: &[u8], dca: &DCAEntry) -> String {
+    dca.target_asset.to_string()
+}
+
+fn dex_idx(_pk: &[u8], dca: &DCAEntry) -> String {
+    dca.dex.clone()
+}
+
+fn schedule_idx(_pk: &[u8], dca: &DCAEntry) -> String {
+    dca.schedule.name().to_owned()
+}
+
+/// Execution records by DCA id and execution number
+pub const DCA_HISTORY: Map<(DCAId, u64), ExecutionRecord> = Map::new("dca_history");
+pub const DCA_STATS: Map<DCAId, ExecutionStats> = Map::new("dca_stats");
+pub const TASK_USAGE: Map<DCAId, TaskUsage> = Map::new("task_usage");
+pub const PENDING_CONVERT: Item<PendingConvert> = Item::new("pending_convert");
+pub const OPERATORS: Map<&Addr, OperatorPermissions> = Map::new("operators");
+/// Last chain cron DCA checked by the sudo handler, the next block continues after it
+pub const CRON_CURSOR: Item<DCAId> = Item::new("cron_cursor");
+/// Chain cron DCAs being converted, in order of their convert replies
+pub const CRON_BATCH: Item<Vec<DCAId>> = Item::new("cron_batch");
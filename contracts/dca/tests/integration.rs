@@ -1,19 +1,17 @@
 mod common;
 
 use abstract_app::abstract_core::{
-    app::BaseQueryMsgFns,
+    app::{BaseMigrateMsg, BaseQueryMsgFns},
     objects::{
-        ans_host::AnsHostError, dependency::DependencyResponse, module_version::ModuleDataResponse,
-        AnsAsset, AssetEntry, DexAssetPairing, PoolAddress, PoolReference, UncheckedContractEntry,
-        UniquePoolId,
+        dependency::DependencyResponse, module_version::ModuleDataResponse, AnsAsset, AssetEntry,
+        DexName, PoolAddress, PoolReference, UncheckedContractEntry, UniquePoolId,
     },
 };
 use abstract_app::abstract_interface::*;
-use abstract_app::abstract_sdk::AbstractSdkError;
 use abstract_client::{AbstractClient, Account, Application, Namespace};
 use abstract_dex_adapter::{interface::DexAdapter, msg::DexInstantiateMsg, DEX_ADAPTER_ID};
 use common::contracts;
-use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, StdError, Uint128};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, Empty, Uint128};
 use croncat_app::{
     contract::{CRONCAT_ID, CRONCAT_MODULE_VERSION},
     croncat_integration_utils::{AGENTS_NAME, MANAGER_NAME, TASKS_NAME},
@@ -24,17 +22,24 @@ use croncat_sdk_factory::msg::{
     ContractMetadataResponse, FactoryInstantiateMsg, FactoryQueryMsg, ModuleInstantiateInfo,
     VersionKind,
 };
-use croncat_sdk_manager::msg::ManagerInstantiateMsg;
-use croncat_sdk_tasks::msg::TasksInstantiateMsg;
+use croncat_sdk_manager::msg::{ManagerExecuteMsg, ManagerInstantiateMsg};
+use croncat_sdk_tasks::msg::{TasksExecuteMsg, TasksInstantiateMsg};
 use cw20::Cw20Coin;
 use cw_asset::AssetInfo;
 // Use prelude to get all the necessary imports
-use cw_orch::mock::cw_multi_test::Executor;
+use cw_orch::mock::cw_multi_test::{AppResponse, Executor};
 use cw_orch::{anyhow, prelude::*};
+use cw_storage_plus::{Item, Map};
 use dca_app::{
     contract::{DCA_APP_ID, DCA_APP_VERSION},
-    msg::{AppInstantiateMsg, ConfigResponse, DCAResponse, Frequency},
-    state::{DCAEntry, DCAId},
+    msg::{
+        AppInstantiateMsg, ConfigResponse, DCABoundary, DCAHistoryResponse, DCALimit, DCAQueryMsg,
+        DCAResponse, DCAStatsResponse, DCASudoMsg, ExecutionTime, FailurePolicy, Frequency,
+        FundingStatusResponse, FundingTotalsResponse, ListDCAsResponse, ListFundingStatusResponse,
+        MigrateMsg, NextExecution, NextExecutionsResponse, OperatorEntry, OperatorPermissions,
+        OperatorsResponse, PriceLimit, QueryMsg, Schedule, SimulateConvertResponse, SourceFunding,
+    },
+    state::{DCAEntry, DCAId, DCAStatus, CRON_BATCH_SIZE},
     *,
 };
 use wyndex_bundle::{WynDex, EUR, USD, WYNDEX};
@@ -65,6 +70,7 @@ const PAUSE_ADMIN: &str = "cosmos338dwgj5wm2tuahvfjdldz5s8hmt7l5aznw8jz9s2mmgj5c
 fn setup_croncat_contracts(
     mock: MockBech32,
     proxy_addr: String,
+    native_denom: &str,
 ) -> anyhow::Result<(CronCatAddrs, Addr)> {
     let sender = mock.sender();
     let pause_admin = mock.addr_make(PAUSE_ADMIN);
@@ -133,7 +139,7 @@ fn setup_croncat_contracts(
             module_instantiate_info,
         },
         &[Coin {
-            denom: DENOM.to_owned(),
+            denom: native_denom.to_owned(),
             amount: Uint128::new(1),
         }],
     )
@@ -268,6 +274,21 @@ fn setup() -> anyhow::Result<(
     AbstractClient<MockBech32>,
     DeployedApps,
     CronCatAddrs,
+)> {
+    setup_with_native_asset("denom", DENOM)
+}
+
+/// Set up the test environment with tasks paid in `native_denom`, registered as `native_asset`
+#[allow(clippy::type_complexity)]
+fn setup_with_native_asset(
+    native_asset: &str,
+    native_denom: &str,
+) -> anyhow::Result<(
+    MockBech32,
+    Account<MockBech32>,
+    AbstractClient<MockBech32>,
+    DeployedApps,
+    CronCatAddrs,
 )> {
     // Create the mock
     let mock = MockBech32::new("mock");
@@ -276,8 +297,13 @@ fn setup() -> anyhow::Result<(
     // With funds
     mock.add_balance(&sender, coins(6_000_000_000, DENOM))?;
     mock.add_balance(&mock.addr_make(AGENT), coins(6_000_000_000, DENOM))?;
+    if native_denom != DENOM {
+        mock.add_balance(&sender, coins(6_000_000_000, native_denom))?;
+        mock.add_balance(&mock.addr_make(AGENT), coins(6_000_000_000, native_denom))?;
+    }
 
-    let (cron_cat_addrs, _) = setup_croncat_contracts(mock.clone(), sender.to_string())?;
+    let (cron_cat_addrs, _) =
+        setup_croncat_contracts(mock.clone(), sender.to_string(), native_denom)?;
 
     // Deploy Abstract to the mock with the client
     let abstract_client = AbstractClient::builder(mock.clone())
@@ -309,10 +335,11 @@ fn setup() -> anyhow::Result<(
     // Publish croncat
     cron_cat_publisher.publish_app::<Croncat<MockBech32>>()?;
 
-    // QUEST #4 You need to deploy Abstract and the dependencies before deploying your app.
-    // We made this super easy! Just use the `AbstractClient` and `Publisher` to deploy the dependencies.
     // Publish the DCA app
-
+    let dca_publisher = abstract_client
+        .publisher_builder(Namespace::from_id(DCA_APP_ID)?)
+        .build()?;
+    dca_publisher.publish_app::<DCA<MockBech32>>()?;
 
     // Create a new account and install the app onto
     let account = abstract_client
@@ -320,7 +347,7 @@ fn setup() -> anyhow::Result<(
         // Note: Dex adapter and croncat app is a dependency of the DCA
         .install_app_with_dependencies::<DCA<MockBech32>>(
             &AppInstantiateMsg {
-                native_asset: AssetEntry::new("denom"),
+                native_asset: AssetEntry::new(native_asset),
                 dca_creation_amount: Uint128::new(5_000_000),
                 refill_threshold: Uint128::new(1_000_000),
                 max_spread: Decimal::percent(30),
@@ -336,6 +363,9 @@ fn setup() -> anyhow::Result<(
         &account.proxy()?,
         vec![coin(50_000_000, DENOM), coin(10_000, EUR)],
     )?;
+    if native_denom != DENOM {
+        mock.add_balance(&account.proxy()?, coins(50_000_000, native_denom))?;
+    }
 
     let cron_cat_app = account.application::<Croncat<MockBech32>>()?;
     let deployed_apps = DeployedApps {
@@ -353,15 +383,6 @@ fn setup() -> anyhow::Result<(
     ))
 }
 
-fn assert_querrier_err_eq<E: std::fmt::Display>(left: CwOrchError, right: E) {
-    let querier_contract_err = || AbstractSdkError::ApiQuery {
-        api: "Adapters".to_owned(),
-        module_id: DCA_APP_ID.to_owned(),
-        error: Box::new(StdError::generic_err(format!("Querier contract error: {right}")).into()),
-    };
-    assert_eq!(left.root().to_string(), querier_contract_err().to_string())
-}
-
 #[test]
 fn successful_install() -> anyhow::Result<()> {
     // Set up the environment and contract
@@ -375,6 +396,13 @@ fn successful_install() -> anyhow::Result<()> {
             dca_creation_amount: Uint128::new(5_000_000),
             refill_threshold: Uint128::new(1_000_000),
             max_spread: Decimal::percent(30),
+            keeper_tip: Uint128::zero(),
+            default_schedule: Schedule::CronCat,
+            fee_rate: Decimal::zero(),
+            fee_recipient: None,
+            gas_limit: 300_000,
+            refill_executions: 0,
+            max_refill_amount: None,
         }
     );
 
@@ -408,17 +436,34 @@ fn create_dca_convert() -> anyhow::Result<()> {
     let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
 
     apps.dca_app.create_dca(
+        None,
+        None,
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
         AnsAsset::new(EUR, 100_u128),
         USD.into(),
     )?;
-    
-    // QUEST #5.0
-    // create dca
-    // Frequency::Cron("0 0 0 1 1 * *".to_owned()),
-    // AnsAsset::new(EUR, 250_u128),
-    // USD.into(),
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Cron("0 0 0 1 1 * *".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 250_u128),
+        USD.into(),
+    )?;
 
     // First dca
     let dca = apps.dca_app.dca(DCAId(1))?;
@@ -429,12 +474,28 @@ fn create_dca_convert() -> anyhow::Result<()> {
                 source_asset: AnsAsset::new(EUR, 100_u128),
                 target_asset: USD.into(),
                 frequency: Frequency::EveryNBlocks(1),
-                dex: WYNDEX.to_owned()
+                dex: WYNDEX.to_owned(),
+                boundary: None,
+                budget: None,
+                max_executions: None,
+                max_spread: None,
+                price_limit: None,
+                spent: Uint128::zero(),
+                executions: 0,
+                status: DCAStatus::Active,
+                schedule: Schedule::CronCat,
+                last_convert_height: None,
+                last_convert_time: None,
+                failure_policy: FailurePolicy::StopOnFail,
+                consecutive_failures: 0,
+                gas_limit: None,
             }),
             pool_references: vec![PoolReference::new(
                 UniquePoolId::new(1),
                 PoolAddress::contract(apps.wyndex.eur_usd_pair.clone())
             )],
+            max_spread: Some(Decimal::percent(30)),
+            gas_limit: Some(300_000),
         }
     );
 
@@ -447,12 +508,28 @@ fn create_dca_convert() -> anyhow::Result<()> {
                 source_asset: AnsAsset::new(EUR, 250_u128),
                 target_asset: USD.into(),
                 frequency: Frequency::Cron("0 0 0 1 1 * *".to_owned()),
-                dex: WYNDEX.to_owned()
+                dex: WYNDEX.to_owned(),
+                boundary: None,
+                budget: None,
+                max_executions: None,
+                max_spread: None,
+                price_limit: None,
+                spent: Uint128::zero(),
+                executions: 0,
+                status: DCAStatus::Active,
+                schedule: Schedule::CronCat,
+                last_convert_height: None,
+                last_convert_time: None,
+                failure_policy: FailurePolicy::StopOnFail,
+                consecutive_failures: 0,
+                gas_limit: None,
             }),
             pool_references: vec![PoolReference::new(
                 UniquePoolId::new(1),
                 PoolAddress::contract(apps.wyndex.eur_usd_pair)
             )],
+            max_spread: Some(Decimal::percent(30)),
+            gas_limit: Some(300_000),
         }
     );
 
@@ -478,35 +555,86 @@ fn create_dca_convert() -> anyhow::Result<()> {
 
 #[test]
 fn create_dca_convert_negative() -> anyhow::Result<()> {
-    let (_mock, _account, abstr, apps, _croncat_addrs) = setup()?;
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
 
     // Not existing pair
     let err = apps.dca_app.create_dca(
+        None,
+        None,
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
         AnsAsset::new(USD, 100_u128),
         USD.into(),
     );
-    assert_querrier_err_eq(
-        err.unwrap_err(),
-        AnsHostError::DexPairingNotFound {
-            pairing: DexAssetPairing::new(AssetEntry::new(USD), AssetEntry::new(USD), WYNDEX),
-            ans_host: abstr.name_service().address()?,
-        },
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::PairNotFound {
+            source_asset: AssetEntry::new(USD),
+            target_asset: AssetEntry::new(USD),
+            dex: WYNDEX.to_owned(),
+        }
+        .to_string()
+    );
+
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 0_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ZeroAmount {}.to_string()
     );
 
     // Bad crontab string
     let err = apps.dca_app.create_dca(
+        None,
+        None,
         WYNDEX.to_owned(),
+        None,
         Frequency::Cron("bad cron".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        None,
         AnsAsset::new(USD, 100_u128),
         EUR.into(),
     );
-    assert_eq!(err.unwrap_err().root().to_string(), "Invalid interval");
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidCronExpression {
+            expression: "bad cron".to_owned()
+        }
+        .to_string()
+    );
 
     apps.dca_app.create_dca(
+        None,
+        None,
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
         AnsAsset::new(EUR, 100_u128),
         USD.into(),
     )?;
@@ -521,160 +649,2475 @@ fn create_dca_convert_negative() -> anyhow::Result<()> {
 }
 
 #[test]
-fn update_dca() -> anyhow::Result<()> {
-    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+fn bounded_dca_completes() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
 
-    // create dca
+    // Budget that isn't a multiple of the swap amount
     apps.dca_app.create_dca(
+        None,
+        Some(Uint128::new(250)),
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
-        AnsAsset::new(EUR, 150_u128),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
         USD.into(),
     )?;
-
-    let task_hash_before_update = apps
-        .cron_cat_app
-        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
-        .task
-        .unwrap()
-        .task_hash;
-
-    apps.dca_app.update_dca(
-        DCAId(1),
-        Some(WYNDEX.into()),
-        Some(Frequency::Cron("0 30 * * * *".to_string())),
-        Some(AnsAsset::new(USD, 200_u128)),
-        Some(EUR.into()),
+    // Limited number of executions
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        Some(1),
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
     )?;
 
-    // QUEST #5.1
-    // call DCA with DCAId 1
+    apps.dca_app.set_sender(&croncat_addrs.manager);
 
-    assert_eq!(
-        dca,
-        DCAResponse {
-            dca: Some(DCAEntry {
-                source_asset: AnsAsset::new(USD, 200_u128),
-                target_asset: EUR.into(),
-                frequency: Frequency::Cron("0 30 * * * *".to_string()),
-                dex: WYNDEX.to_owned()
-            }),
-            pool_references: vec![PoolReference::new(
-                UniquePoolId::new(1),
-                PoolAddress::contract(apps.wyndex.eur_usd_pair.clone())
-            )],
-        }
-    );
+    apps.dca_app.convert(DCAId(1))?;
+    apps.dca_app.convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.spent, Uint128::new(200));
+    assert_eq!(dca.executions, 2);
+    assert_eq!(dca.status, DCAStatus::Active);
 
-    let task_hash_after_update = apps
-        .cron_cat_app
-        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
-        .task
-        .unwrap()
-        .task_hash;
+    // Last execution only spends what's left of the budget
+    apps.dca_app.convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.spent, Uint128::new(250));
+    assert_eq!(dca.executions, 3);
+    assert_eq!(dca.status, DCAStatus::Completed);
 
-    assert_ne!(task_hash_before_update, task_hash_after_update);
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_balance, Uint128::new(9750));
 
-    // Now without updating frequency
+    apps.dca_app.convert(DCAId(2))?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.spent, Uint128::new(100));
+    assert_eq!(dca.executions, 1);
+    assert_eq!(dca.status, DCAStatus::Completed);
+
+    // Removing the limit makes the DCA unbounded again
+    apps.dca_app.set_sender(&mock.sender());
     apps.dca_app.update_dca(
-        DCAId(1),
+        Some(vec![DCALimit::MaxExecutions]),
+        DCAId(2),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
         None,
         None,
-        Some(AnsAsset::new(USD, 250_u128)),
         None,
     )?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.max_executions, None);
+    assert_eq!(dca.status, DCAStatus::Active);
 
-    let dca = apps.dca_app.dca(DCAId(1))?;
-    assert_eq!(
-        dca,
-        DCAResponse {
-            dca: Some(DCAEntry {
-                source_asset: AnsAsset::new(USD, 250_u128),
-                target_asset: AssetEntry::new(EUR),
-                frequency: Frequency::Cron("0 30 * * * *".to_string()),
-                dex: WYNDEX.to_owned()
-            }),
-            pool_references: vec![PoolReference::new(
-                UniquePoolId::new(1),
-                PoolAddress::contract(apps.wyndex.eur_usd_pair)
-            )],
-        }
-    );
-
-    let task_hash_after_second_update = apps
-        .cron_cat_app
-        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
-        .task
-        .unwrap()
-        .task_hash;
-
-    assert_eq!(task_hash_after_update, task_hash_after_second_update);
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(2))?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.executions, 2);
+    assert_eq!(dca.status, DCAStatus::Active);
 
     Ok(())
 }
 
 #[test]
-fn update_dca_negative() -> anyhow::Result<()> {
-    let (_mock, _account, abstr, apps, _croncat_addrs) = setup()?;
+fn bounded_dca_negative() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
 
-    // create dca
-    apps.dca_app.create_dca(
+    let err = apps.dca_app.create_dca(
+        None,
+        Some(Uint128::zero()),
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
-        AnsAsset::new(EUR, 150_u128),
-        USD.into(),
-    )?;
-
-    // Not existing pair
-    let err = apps.dca_app.update_dca(
-        DCAId(1),
         None,
         None,
-        Some(AnsAsset::new(USD, 200_u128)),
-        Some(USD.into()),
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
     );
-
-    assert_querrier_err_eq(
-        err.unwrap_err(),
-        AnsHostError::DexPairingNotFound {
-            pairing: DexAssetPairing::new(AssetEntry::new(USD), AssetEntry::new(USD), WYNDEX),
-            ans_host: abstr.name_service().address()?,
-        },
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ZeroLimit {}.to_string()
     );
 
-    // Bad crontab string
-    let err = apps.dca_app.update_dca(
-        DCAId(1),
+    let err = apps.dca_app.create_dca(
         None,
-        Some(Frequency::Cron("bad cron".to_owned())),
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        Some(0),
         None,
         None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ZeroLimit {}.to_string()
     );
-    assert_eq!(err.unwrap_err().root().to_string(), "Invalid interval");
 
     Ok(())
 }
 
 #[test]
-fn cancel_dca() -> anyhow::Result<()> {
-    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+fn bounded_schedule() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
 
-    // create dca
+    let height = mock.block_info()?.height;
+    let boundary = DCABoundary::Height {
+        start: Some(height + 100),
+        end: Some(height + 200),
+    };
     apps.dca_app.create_dca(
+        Some(boundary.clone()),
+        None,
         WYNDEX.to_owned(),
+        None,
         Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
         AnsAsset::new(EUR, 100_u128),
         USD.into(),
     )?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.boundary, Some(boundary));
 
-    apps.dca_app.cancel_dca(DCAId(1))?;
-
-    let dca = apps.dca_app.dca(DCAId(1))?;
-    assert_eq!(
-        dca,
-        DCAResponse {
-            dca: None,
-            pool_references: vec![]
+    // Boundary is part of the task, so it gets re-created
+    let task_hash_before_update = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+    let boundary = DCABoundary::Height {
+        start: None,
+        end: Some(height + 300),
+    };
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        Some(boundary.clone()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.boundary, Some(boundary));
+    let task_hash_after_update = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+    assert_ne!(task_hash_before_update, task_hash_after_update);
+
+    // Start after end
+    let err = apps.dca_app.create_dca(
+        Some(DCABoundary::Height {
+            start: Some(height + 200),
+            end: Some(height + 100),
+        }),
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidBoundary {}.to_string()
+    );
+
+    // End in the past
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        Some(DCABoundary::Height {
+            start: None,
+            end: Some(height),
+        }),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidBoundary {}.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn price_limit_skips_convert() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    // Pool price is around 1 EUR per USD
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        Some(PriceLimit::MaxBuyPrice(Decimal::percent(50))),
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_balance, Uint128::new(10_000));
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 0);
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(1) }))?;
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(stats.average_price, None);
+
+    apps.dca_app.set_sender(&mock.sender());
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(PriceLimit::MaxBuyPrice(Decimal::percent(200))),
+        None,
+        None,
+    )?;
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_balance, Uint128::new(9900));
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 1);
+
+    Ok(())
+}
+
+#[test]
+fn max_spread_override() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        Some(Decimal::percent(5)),
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let dca = apps.dca_app.dca(DCAId(1))?;
+    assert_eq!(dca.max_spread, Some(Decimal::percent(5)));
+
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Decimal::permille(5)),
+        None,
+        None,
+        None,
+    )?;
+    let dca = apps.dca_app.dca(DCAId(1))?;
+    assert_eq!(dca.max_spread, Some(Decimal::permille(5)));
+
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Decimal::percent(101)),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidMaxSpread {}.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_dcas() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    for (source_asset, target_asset) in [(EUR, USD), (USD, EUR), (EUR, USD), (EUR, USD)] {
+        apps.dca_app.create_dca(
+            None,
+            None,
+            WYNDEX.to_owned(),
+            None,
+            Frequency::EveryNBlocks(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+            AnsAsset::new(source_asset, 100_u128),
+            target_asset.into(),
+        )?;
+    }
+    // Leave a gap in ids
+    apps.dca_app.cancel_dca(DCAId(3))?;
+
+    let list_dcas = |start_after: Option<DCAId>,
+                     limit: Option<u32>,
+                     source_asset: Option<&str>|
+     -> anyhow::Result<Vec<DCAId>> {
+        let response: ListDCAsResponse =
+            apps.dca_app.query(&QueryMsg::from(DCAQueryMsg::ListDCAs {
+                start_after,
+                limit,
+                source_asset: source_asset.map(AssetEntry::new),
+                target_asset: None,
+                dex: None,
+            }))?;
+        Ok(response
+            .dcas
+            .into_iter()
+            .map(|entry| entry.dca_id)
+            .collect())
+    };
+
+    assert_eq!(
+        list_dcas(None, None, None)?,
+        vec![DCAId(1), DCAId(2), DCAId(4)]
+    );
+    assert_eq!(list_dcas(None, Some(1), None)?, vec![DCAId(1)]);
+    assert_eq!(list_dcas(Some(DCAId(2)), None, None)?, vec![DCAId(4)]);
+    assert_eq!(list_dcas(None, None, Some(USD))?, vec![DCAId(2)]);
+    assert_eq!(
+        list_dcas(Some(DCAId(1)), Some(1), Some(EUR))?,
+        vec![DCAId(4)]
+    );
+
+    // Filter by target asset and dex
+    let response: ListDCAsResponse =
+        apps.dca_app.query(&QueryMsg::from(DCAQueryMsg::ListDCAs {
+            start_after: None,
+            limit: None,
+            source_asset: None,
+            target_asset: Some(USD.into()),
+            dex: Some(WYNDEX.to_owned()),
+        }))?;
+    let dca_ids: Vec<DCAId> = response
+        .dcas
+        .into_iter()
+        .map(|entry| entry.dca_id)
+        .collect();
+    assert_eq!(dca_ids, vec![DCAId(1), DCAId(4)]);
+
+    Ok(())
+}
+
+/// DCA entry as stored by the app before the indexes were added
+#[cosmwasm_schema::cw_serde]
+struct LegacyDCAEntry {
+    source_asset: AnsAsset,
+    target_asset: AssetEntry,
+    frequency: Frequency,
+    dex: String,
+}
+
+#[cosmwasm_schema::cw_serde]
+struct ContractVersion {
+    contract: String,
+    version: String,
+}
+
+#[test]
+fn migrate_legacy_dcas() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // Roll the app back to the previous version with entries it stored
+    let dca_addr = apps.dca_app.address()?;
+    {
+        let mut app = mock.app.borrow_mut();
+        let mut storage = app.contract_storage_mut(&dca_addr);
+        let legacy_list: Map<u64, LegacyDCAEntry> = Map::new("dca_list");
+        for (dca_id, source_asset, target_asset) in [(1, EUR, USD), (2, USD, EUR)] {
+            legacy_list.save(
+                storage.as_mut(),
+                dca_id,
+                &LegacyDCAEntry {
+                    source_asset: AnsAsset::new(source_asset, 100_u128),
+                    target_asset: target_asset.into(),
+                    frequency: Frequency::EveryNBlocks(1),
+                    dex: WYNDEX.to_owned(),
+                },
+            )?;
+        }
+        Item::new("contract_info").save(
+            storage.as_mut(),
+            &ContractVersion {
+                contract: DCA_APP_ID.to_owned(),
+                version: "0.3.0".to_owned(),
+            },
+        )?;
+    }
+
+    apps.dca_app.call_as(&account.manager()?).migrate(
+        &MigrateMsg {
+            base: BaseMigrateMsg {},
+            module: Empty {},
+        },
+        apps.dca_app.code_id()?,
+    )?;
+
+    // Legacy entries are readable and indexed
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.source_asset, AnsAsset::new(USD, 100_u128));
+    assert_eq!(dca.status, DCAStatus::Active);
+    assert_eq!(dca.executions, 0);
+
+    let list_dcas = |source_asset: Option<AssetEntry>,
+                     target_asset: Option<AssetEntry>,
+                     dex: Option<DexName>|
+     -> anyhow::Result<Vec<DCAId>> {
+        let response: ListDCAsResponse =
+            apps.dca_app.query(&QueryMsg::from(DCAQueryMsg::ListDCAs {
+                start_after: None,
+                limit: None,
+                source_asset,
+                target_asset,
+                dex,
+            }))?;
+        Ok(response
+            .dcas
+            .into_iter()
+            .map(|entry| entry.dca_id)
+            .collect())
+    };
+    assert_eq!(list_dcas(Some(EUR.into()), None, None)?, vec![DCAId(1)]);
+    assert_eq!(list_dcas(None, Some(EUR.into()), None)?, vec![DCAId(2)]);
+    assert_eq!(
+        list_dcas(None, None, Some(WYNDEX.to_owned()))?,
+        vec![DCAId(1), DCAId(2)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dca_history() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+    mock.wait_blocks(1)?;
+    apps.dca_app.convert(DCAId(1))?;
+
+    let history: DCAHistoryResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::DCAHistory {
+                dca_id: DCAId(1),
+                start_after: None,
+                limit: None,
+            }))?;
+    assert_eq!(history.executions.len(), 2);
+
+    let first = &history.executions[0];
+    assert_eq!(first.execution, 1);
+    assert_eq!(first.record.source_spent, Uint128::new(100));
+    assert_eq!(first.record.target_received, Uint128::new(98));
+    assert_eq!(first.record.price, Decimal::from_ratio(100_u128, 98_u128));
+    assert_eq!(first.record.dex, WYNDEX.to_owned());
+
+    let second = &history.executions[1];
+    assert_eq!(second.execution, 2);
+    assert_eq!(second.record.source_spent, Uint128::new(100));
+    assert_eq!(second.record.block_height, first.record.block_height + 1);
+
+    let history: DCAHistoryResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::DCAHistory {
+                dca_id: DCAId(1),
+                start_after: Some(1),
+                limit: None,
+            }))?;
+    assert_eq!(history.executions, vec![second.clone()]);
+
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(1) }))?;
+    let target_received = first.record.target_received + second.record.target_received;
+    assert_eq!(
+        stats,
+        DCAStatsResponse {
+            source_spent: Uint128::new(200),
+            target_received,
+            average_price: Some(Decimal::from_ratio(Uint128::new(200), target_received)),
+            executions: 2,
+            skipped: 0,
+            failed: 0,
+            fees_paid: Uint128::zero(),
+            first_execution: Some(first.record.time),
+            last_execution: Some(second.record.time),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn native_denom_source() -> anyhow::Result<()> {
+    // Tasks are paid in the source asset
+    let (mock, account, _abstr, apps, croncat_addrs) = setup_with_native_asset(EUR, EUR)?;
+
+    // Refill on every convert
+    apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Uint128::new(1_000_000_000)),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+
+    mock.wait_blocks(1)?;
+    mock.app.borrow_mut().execute_contract(
+        mock.addr_make(AGENT),
+        croncat_addrs.manager.clone(),
+        &ManagerExecuteMsg::ProxyCall(None),
+        &[],
+    )?;
+
+    // Refill is sent in the same asset, but isn't recorded as spent
+    let eur_spent = eur_balance - mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_spent, Uint128::new(5_000_100));
+    let history: DCAHistoryResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::DCAHistory {
+                dca_id: DCAId(1),
+                start_after: None,
+                limit: None,
+            }))?;
+    assert_eq!(history.executions.len(), 1);
+    let record = &history.executions[0].record;
+    assert_eq!(record.source_spent, Uint128::new(100));
+    assert_eq!(record.target_received, Uint128::new(98));
+
+    Ok(())
+}
+
+#[test]
+fn pause_resume_dca() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let task_hash_before_pause = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+
+    apps.dca_app.pause_dca(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Paused);
+
+    let err = apps.dca_app.pause_dca(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::DCAPaused {}.to_string()
+    );
+
+    // Settings can be changed while paused
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Frequency::EveryNBlocks(2)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Paused);
+
+    apps.dca_app.resume_dca(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Active);
+    assert_eq!(dca.frequency, Frequency::EveryNBlocks(2));
+
+    let task_hash_after_resume = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+    assert_ne!(task_hash_before_pause, task_hash_after_resume);
+
+    let err = apps.dca_app.resume_dca(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::DCANotPaused {}.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn convert_with_fee() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+    let fee_recipient = mock.addr_make("fee_recipient");
+
+    // Fee is bounded and needs a recipient
+    let err = apps.dca_app.update_config(
+        None,
+        Some(Decimal::percent(50)),
+        Some(fee_recipient.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::FeeTooHigh {
+            max: Decimal::percent(10)
+        }
+        .to_string()
+    );
+    let err = apps.dca_app.update_config(
+        None,
+        Some(Decimal::percent(1)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::MissingFeeRecipient {}.to_string()
+    );
+
+    apps.dca_app.update_config(
+        None,
+        Some(Decimal::percent(1)),
+        Some(fee_recipient.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 500_u128),
+        USD.into(),
+    )?;
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+
+    let fee_balance = mock.query_balance(&fee_recipient, EUR)?;
+    assert_eq!(fee_balance, Uint128::new(5));
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_balance, Uint128::new(9_500));
+
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(1) }))?;
+    assert_eq!(stats.source_spent, Uint128::new(495));
+    assert_eq!(stats.fees_paid, Uint128::new(5));
+
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.spent, Uint128::new(500));
+
+    Ok(())
+}
+
+#[test]
+fn keeper_convert() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // New DCAs use keeper schedule by default
+    apps.dca_app.update_config(
+        Some(Schedule::Keeper),
+        None,
+        None,
+        None,
+        Some(Uint128::new(1_000)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(5),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    // No croncat task for keeper DCA
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task;
+    assert!(task.is_none());
+
+    // Anyone can convert once due and gets a tip
+    let keeper = mock.addr_make("keeper");
+    apps.dca_app.call_as(&keeper).convert(DCAId(1))?;
+
+    let usd_balance = mock.query_balance(&account.proxy()?, USD)?;
+    assert_eq!(usd_balance, Uint128::new(98));
+    let tip_balance = mock.query_balance(&keeper, DENOM)?;
+    assert_eq!(tip_balance, Uint128::new(1_000));
+
+    let err = apps.dca_app.call_as(&keeper).convert(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::NotDue {}.to_string()
+    );
+
+    mock.wait_blocks(5)?;
+    apps.dca_app.call_as(&keeper).convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 2);
+
+    // Boundary end block is still converted
+    let height = mock.block_info()?.height;
+    apps.dca_app.create_dca(
+        Some(DCABoundary::Height {
+            start: None,
+            end: Some(height + 5),
+        }),
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(5),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.call_as(&keeper).convert(DCAId(2))?;
+    mock.wait_blocks(5)?;
+    apps.dca_app.call_as(&keeper).convert(DCAId(2))?;
+    mock.wait_blocks(5)?;
+    let err = apps.dca_app.call_as(&keeper).convert(DCAId(2));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::NotDue {}.to_string()
+    );
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.executions, 2);
+
+    // Keeper can't follow cron schedule
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Cron("0 0 * * * *".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ScheduleFrequencyUnsupported {}.to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn chain_cron_convert() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(2),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::ChainCron),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    // Not handled by chain cron
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    // Only the app itself can convert chain cron DCA
+    let err = apps.dca_app.convert(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::NotChainCronConvert {}.to_string()
+    );
+
+    let sudo = |mock: &MockBech32| -> anyhow::Result<()> {
+        mock.app
+            .borrow_mut()
+            .wasm_sudo(apps.dca_app.address()?, &DCASudoMsg::Cron {})?;
+        Ok(())
+    };
+
+    sudo(&mock)?;
+    let usd_balance = mock.query_balance(&account.proxy()?, USD)?;
+    assert_eq!(usd_balance, Uint128::new(98));
+
+    // Not due yet
+    mock.wait_blocks(1)?;
+    sudo(&mock)?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 1);
+
+    mock.wait_blocks(1)?;
+    sudo(&mock)?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 2);
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.executions, 0);
+
+    // Unfunded DCA fails without stopping other DCAs from converting
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(2),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::ChainCron),
+        AnsAsset::new(USD, 1_000_u128),
+        EUR.into(),
+    )?;
+    mock.wait_blocks(2)?;
+    sudo(&mock)?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 3);
+    let dca = apps.dca_app.dca(DCAId(3))?.dca.unwrap();
+    assert_eq!(dca.executions, 0);
+    assert_eq!(dca.status, DCAStatus::Paused);
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(3) }))?;
+    assert_eq!(stats.failed, 1);
+
+    Ok(())
+}
+
+#[test]
+fn chain_cron_batch() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    let dca_count = CRON_BATCH_SIZE as u64 + 1;
+    for _ in 0..dca_count {
+        apps.dca_app.create_dca(
+            None,
+            None,
+            WYNDEX.to_owned(),
+            None,
+            Frequency::EveryNBlocks(1_000),
+            None,
+            None,
+            None,
+            None,
+            Some(Schedule::ChainCron),
+            AnsAsset::new(EUR, 100_u128),
+            USD.into(),
+        )?;
+    }
+
+    let sudo = |mock: &MockBech32| -> anyhow::Result<()> {
+        mock.app
+            .borrow_mut()
+            .wasm_sudo(apps.dca_app.address()?, &DCASudoMsg::Cron {})?;
+        Ok(())
+    };
+    let executions = |dca_id: u64| -> anyhow::Result<u64> {
+        Ok(apps.dca_app.dca(DCAId(dca_id))?.dca.unwrap().executions)
+    };
+
+    // Amount of DCAs checked per block is capped
+    sudo(&mock)?;
+    for dca_id in 1..dca_count {
+        assert_eq!(executions(dca_id)?, 1);
+    }
+    assert_eq!(executions(dca_count)?, 0);
+
+    // Next block continues after the last checked DCA
+    mock.wait_blocks(1)?;
+    sudo(&mock)?;
+    assert_eq!(executions(dca_count)?, 1);
+    assert_eq!(executions(1)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn simulate_convert() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    let simulation: SimulateConvertResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::SimulateDCA {
+                source_asset: AnsAsset::new(EUR, 100_u128),
+                target_asset: USD.into(),
+                dex: WYNDEX.to_owned(),
+            }))?;
+    assert_eq!(simulation.offer_asset, AnsAsset::new(EUR, 100_u128));
+    assert_eq!(simulation.fee, Uint128::zero());
+    assert!(!simulation.return_amount.is_zero());
+    assert_eq!(simulation.pool_references.len(), 1);
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let convert_simulation: SimulateConvertResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::SimulateConvert {
+                dca_id: DCAId(1),
+            }))?;
+    assert_eq!(convert_simulation, simulation);
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+    let usd_balance = mock.query_balance(&account.proxy()?, USD)?;
+    assert!(usd_balance <= simulation.return_amount);
+
+    Ok(())
+}
+
+#[test]
+fn one_shot_dca() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, mut apps, croncat_addrs) = setup()?;
+    let height = mock.block_info()?.height;
+
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Once(ExecutionTime::Height(height)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ExecutionTimeInPast {}.to_string()
+    );
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Once(ExecutionTime::Height(height + 10)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Immediate,
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    let next: NextExecutionsResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::NextExecutions {
+                dca_id: DCAId(1),
+                count: 5,
+            }))?;
+    assert_eq!(next.executions, vec![NextExecution::Height(height + 10)]);
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(2))?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Completed);
+    assert_eq!(dca.executions, 1);
+
+    mock.wait_blocks(10)?;
+    apps.dca_app.convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Completed);
+
+    let next: NextExecutionsResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::NextExecutions {
+                dca_id: DCAId(1),
+                count: 5,
+            }))?;
+    assert!(next.executions.is_empty());
+
+    // Completed single purchase can't be made again
+    apps.dca_app.set_sender(&mock.sender());
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(2),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::DCACompleted {}.to_string()
+    );
+
+    // Skipped single purchase expires without buying
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Immediate,
+        None,
+        None,
+        None,
+        Some(PriceLimit::MaxBuyPrice(Decimal::percent(1))),
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(3))?;
+    let dca = apps.dca_app.dca(DCAId(3))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Expired);
+    assert_eq!(dca.executions, 0);
+
+    // Expired DCA gets another try on update
+    apps.dca_app.set_sender(&mock.sender());
+    apps.dca_app.update_dca(
+        Some(vec![DCALimit::PriceLimit]),
+        DCAId(3),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    let dca = apps.dca_app.dca(DCAId(3))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Active);
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(3))?;
+    let dca = apps.dca_app.dca(DCAId(3))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Completed);
+    assert_eq!(dca.executions, 1);
+
+    // Unfunded single purchase expires too, instead of staying active without a task
+    apps.dca_app.set_sender(&mock.sender());
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Immediate,
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_000_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(4))?;
+    let dca = apps.dca_app.dca(DCAId(4))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Expired);
+    assert_eq!(dca.executions, 0);
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(4) }))?;
+    assert_eq!(stats.failed, 1);
+
+    Ok(())
+}
+
+#[test]
+fn next_executions() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Cron("every monday".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidCronExpression {
+            expression: "every monday".to_owned()
+        }
+        .to_string()
+    );
+
+    // Hourly
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::Cron("0 0 * * * *".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let next: NextExecutionsResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::NextExecutions {
+                dca_id: DCAId(1),
+                count: 3,
+            }))?;
+    let block_time = mock.block_info()?.time;
+    let times: Vec<_> = next
+        .executions
+        .iter()
+        .map(|execution| match execution {
+            NextExecution::Time(time) => *time,
+            NextExecution::Height(_) => panic!("expected time"),
+        })
+        .collect();
+    assert_eq!(times.len(), 3);
+    assert!(times[0] > block_time);
+    assert!(times[0] <= block_time.plus_seconds(3600));
+    assert_eq!(times[0].seconds() % 3600, 0);
+    assert_eq!(times[1], times[0].plus_seconds(3600));
+    assert_eq!(times[2], times[1].plus_seconds(3600));
+
+    // Limited to the remaining executions
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(5),
+        None,
+        Some(2),
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let next: NextExecutionsResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::NextExecutions {
+                dca_id: DCAId(2),
+                count: 5,
+            }))?;
+    let height = mock.block_info()?.height;
+    let first = height + 5 - height % 5;
+    assert_eq!(
+        next.executions,
+        vec![
+            NextExecution::Height(first),
+            NextExecution::Height(first + 5)
+        ]
+    );
+
+    // Heights that would overflow are left out
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(u64::MAX),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let next: NextExecutionsResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::NextExecutions {
+                dca_id: DCAId(3),
+                count: 5,
+            }))?;
+    assert_eq!(next.executions, vec![NextExecution::Height(height)]);
+
+    Ok(())
+}
+
+#[test]
+fn operators() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+    let operator = mock.addr_make("operator");
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    let permissions = OperatorPermissions {
+        update: true,
+        pause: true,
+        ..Default::default()
+    };
+    apps.dca_app
+        .set_operator(operator.to_string(), permissions.clone())?;
+
+    let operators: OperatorsResponse =
+        apps.dca_app.query(&QueryMsg::from(DCAQueryMsg::Operators {
+            start_after: None,
+            limit: None,
+        }))?;
+    assert_eq!(
+        operators.operators,
+        vec![OperatorEntry {
+            operator: operator.clone(),
+            permissions,
+        }]
+    );
+
+    // Operator can only do what is permitted
+    let operator_app = apps.dca_app.call_as(&operator);
+    operator_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Frequency::EveryNBlocks(2)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    operator_app.pause_dca(DCAId(1))?;
+    operator_app.resume_dca(DCAId(1))?;
+
+    let err = operator_app.cancel_dca(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::Unauthorized {
+            action: "cancel".to_owned()
+        }
+        .to_string()
+    );
+    let err = operator_app.set_operator(operator.to_string(), OperatorPermissions::default());
+    assert!(err.is_err());
+
+    apps.dca_app.remove_operator(operator.to_string())?;
+    let err = operator_app.pause_dca(DCAId(1));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::Unauthorized {
+            action: "pause".to_owned()
+        }
+        .to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn update_dca() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // create dca
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 150_u128),
+        USD.into(),
+    )?;
+
+    let task_hash_before_update = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        Some(WYNDEX.into()),
+        None,
+        Some(Frequency::Cron("0 30 * * * *".to_string())),
+        None,
+        None,
+        None,
+        None,
+        Some(AnsAsset::new(USD, 200_u128)),
+        Some(EUR.into()),
+    )?;
+
+    let dca = apps.dca_app.dca(DCAId(1))?;
+    assert_eq!(
+        dca,
+        DCAResponse {
+            dca: Some(DCAEntry {
+                source_asset: AnsAsset::new(USD, 200_u128),
+                target_asset: EUR.into(),
+                frequency: Frequency::Cron("0 30 * * * *".to_string()),
+                dex: WYNDEX.to_owned(),
+                boundary: None,
+                budget: None,
+                max_executions: None,
+                max_spread: None,
+                price_limit: None,
+                spent: Uint128::zero(),
+                executions: 0,
+                status: DCAStatus::Active,
+                schedule: Schedule::CronCat,
+                last_convert_height: None,
+                last_convert_time: None,
+                failure_policy: FailurePolicy::StopOnFail,
+                consecutive_failures: 0,
+                gas_limit: None,
+            }),
+            pool_references: vec![PoolReference::new(
+                UniquePoolId::new(1),
+                PoolAddress::contract(apps.wyndex.eur_usd_pair.clone())
+            )],
+            max_spread: Some(Decimal::percent(30)),
+            gas_limit: Some(300_000),
+        }
+    );
+
+    let task_hash_after_update = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+
+    assert_ne!(task_hash_before_update, task_hash_after_update);
+
+    // Now without updating frequency
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(AnsAsset::new(USD, 250_u128)),
+        None,
+    )?;
+
+    let dca = apps.dca_app.dca(DCAId(1))?;
+    assert_eq!(
+        dca,
+        DCAResponse {
+            dca: Some(DCAEntry {
+                source_asset: AnsAsset::new(USD, 250_u128),
+                target_asset: AssetEntry::new(EUR),
+                frequency: Frequency::Cron("0 30 * * * *".to_string()),
+                dex: WYNDEX.to_owned(),
+                boundary: None,
+                budget: None,
+                max_executions: None,
+                max_spread: None,
+                price_limit: None,
+                spent: Uint128::zero(),
+                executions: 0,
+                status: DCAStatus::Active,
+                schedule: Schedule::CronCat,
+                last_convert_height: None,
+                last_convert_time: None,
+                failure_policy: FailurePolicy::StopOnFail,
+                consecutive_failures: 0,
+                gas_limit: None,
+            }),
+            pool_references: vec![PoolReference::new(
+                UniquePoolId::new(1),
+                PoolAddress::contract(apps.wyndex.eur_usd_pair)
+            )],
+            max_spread: Some(Decimal::percent(30)),
+            gas_limit: Some(300_000),
+        }
+    );
+
+    let task_hash_after_second_update = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+
+    assert_eq!(task_hash_after_update, task_hash_after_second_update);
+
+    Ok(())
+}
+
+#[test]
+fn update_dca_negative() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // create dca
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 150_u128),
+        USD.into(),
+    )?;
+
+    // Not existing pair
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(AnsAsset::new(USD, 200_u128)),
+        Some(USD.into()),
+    );
+
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::PairNotFound {
+            source_asset: AssetEntry::new(USD),
+            target_asset: AssetEntry::new(USD),
+            dex: WYNDEX.to_owned(),
+        }
+        .to_string()
+    );
+
+    // Only dex changed
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        Some("unknown_dex".to_owned()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::PairNotFound {
+            source_asset: AssetEntry::new(EUR),
+            target_asset: AssetEntry::new(USD),
+            dex: "unknown_dex".to_owned(),
+        }
+        .to_string()
+    );
+
+    // Bad crontab string
+    let err = apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Frequency::Cron("bad cron".to_owned())),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::InvalidCronExpression {
+            expression: "bad cron".to_owned()
+        }
+        .to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cancel_dca() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // create dca
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    apps.dca_app.cancel_dca(DCAId(1))?;
+
+    let dca = apps.dca_app.dca(DCAId(1))?;
+    assert_eq!(
+        dca,
+        DCAResponse {
+            dca: None,
+            pool_references: vec![],
+            max_spread: None,
+            gas_limit: None,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dca_errors() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    // Missing dca
+    let err = apps.dca_app.cancel_dca(DCAId(5));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::DCANotFound { dca_id: DCAId(5) }.to_string()
+    );
+    let err = apps.dca_app.pause_dca(DCAId(5));
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::DCANotFound { dca_id: DCAId(5) }.to_string()
+    );
+    let err = apps
+        .dca_app
+        .query::<DCAStatsResponse>(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(5) }))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&error::DCAError::DCANotFound { dca_id: DCAId(5) }.to_string()));
+    let err = apps
+        .dca_app
+        .query::<DCAHistoryResponse>(&QueryMsg::from(DCAQueryMsg::DCAHistory {
+            dca_id: DCAId(5),
+            start_after: None,
+            limit: None,
+        }))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&error::DCAError::DCANotFound { dca_id: DCAId(5) }.to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn recreate_missing_task() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let task_hash = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap()
+        .task_hash;
+
+    // Task removed behind the DCA app's back
+    mock.app.borrow_mut().execute_contract(
+        account.proxy()?,
+        croncat_addrs.tasks.clone(),
+        &TasksExecuteMsg::RemoveTask { task_hash },
+        &[],
+    )?;
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task;
+    assert!(task.is_none());
+
+    // Convert still goes through and recreates the task
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task;
+    assert!(task.is_some());
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.executions, 1);
+
+    Ok(())
+}
+
+#[test]
+fn failure_policy() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    // Zero tolerance is meaningless
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        Some(FailurePolicy::PauseAfter(0)),
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(
+        err.unwrap_err().root().to_string(),
+        error::DCAError::ZeroFailureTolerance {}.to_string()
+    );
+
+    // Both swaps always fail on the spread
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        Some(FailurePolicy::PauseAfter(2)),
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        Some(Decimal::permille(1)),
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        Some(FailurePolicy::Never),
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        Some(Decimal::permille(1)),
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    // Failed swap doesn't fail the convert
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.consecutive_failures, 1);
+    assert_eq!(dca.status, DCAStatus::Active);
+    assert_eq!(dca.spent, Uint128::zero());
+    assert_eq!(dca.executions, 0);
+
+    // Paused once tolerance is reached
+    apps.dca_app.convert(DCAId(1))?;
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.consecutive_failures, 2);
+    assert_eq!(dca.status, DCAStatus::Paused);
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task;
+    assert!(task.is_none());
+
+    // Never stops
+    for _ in 0..3 {
+        apps.dca_app.convert(DCAId(2))?;
+    }
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.consecutive_failures, 3);
+    assert_eq!(dca.status, DCAStatus::Active);
+
+    Ok(())
+}
+
+#[test]
+fn unfunded_dca_pauses() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        Some(FailurePolicy::PauseAfter(2)),
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    // Account doesn't hold enough source asset
+    mock.set_balance(
+        &account.proxy()?,
+        vec![coin(50_000_000, DENOM), coin(50, EUR)],
+    )?;
+
+    // Missing funds count as a failed swap instead of failing the convert
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    let res = apps.dca_app.convert(DCAId(1))?;
+    let swap_error = res
+        .events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == "swap_error")
+        .map(|attr| attr.value.clone());
+    assert_eq!(
+        swap_error,
+        Some(
+            error::DCAError::InsufficientSourceBalance {
+                needed: Uint128::new(100),
+                available: Uint128::new(50),
+            }
+            .to_string()
+        )
+    );
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Paused);
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(1) }))?;
+    assert_eq!(stats.failed, 1);
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task;
+    assert!(task.is_none());
+
+    // Paused once tolerance is reached
+    apps.dca_app.convert(DCAId(2))?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Active);
+    apps.dca_app.convert(DCAId(2))?;
+    let dca = apps.dca_app.dca(DCAId(2))?.dca.unwrap();
+    assert_eq!(dca.status, DCAStatus::Paused);
+    assert_eq!(dca.consecutive_failures, 2);
+
+    Ok(())
+}
+
+#[test]
+fn failed_swap_keeps_convert() -> anyhow::Result<()> {
+    let (mock, account, _abstr, mut apps, croncat_addrs) = setup()?;
+    let fee_recipient = mock.addr_make("fee_recipient");
+
+    apps.dca_app.update_config(
+        None,
+        Some(Decimal::percent(1)),
+        Some(fee_recipient.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    // Swap always fails on the spread
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        Some(FailurePolicy::Never),
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        Some(Decimal::permille(1)),
+        None,
+        None,
+        AnsAsset::new(EUR, 500_u128),
+        USD.into(),
+    )?;
+
+    apps.dca_app.set_sender(&croncat_addrs.manager);
+    apps.dca_app.convert(DCAId(1))?;
+
+    // Nothing swapped and no fee charged
+    let eur_balance = mock.query_balance(&account.proxy()?, EUR)?;
+    assert_eq!(eur_balance, Uint128::new(10_000));
+    let fee_balance = mock.query_balance(&fee_recipient, EUR)?;
+    assert_eq!(fee_balance, Uint128::zero());
+
+    // But the failure is recorded
+    let stats: DCAStatsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::DCAStats { dca_id: DCAId(1) }))?;
+    assert_eq!(stats.failed, 1);
+    assert_eq!(stats.executions, 0);
+    assert_eq!(stats.fees_paid, Uint128::zero());
+    let dca = apps.dca_app.dca(DCAId(1))?.dca.unwrap();
+    assert_eq!(dca.last_convert_height, Some(mock.block_info()?.height));
+    assert_eq!(dca.spent, Uint128::zero());
+
+    Ok(())
+}
+
+#[test]
+fn gas_limit() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _croncat_addrs) = setup()?;
+    let gas_limit_err = error::DCAError::GasLimitOutOfBounds {
+        min: 100_000,
+        max: 3_000_000,
+    }
+    .to_string();
+
+    let err = apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        Some(50),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(err.unwrap_err().root().to_string(), gas_limit_err);
+    let err = apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        Some(10_000_000),
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    );
+    assert_eq!(err.unwrap_err().root().to_string(), gas_limit_err);
+
+    // Config default is used for new tasks
+    apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        Some(400_000),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    assert_eq!(apps.dca_app.dca(DCAId(1))?.gas_limit, Some(400_000));
+    let task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap();
+    assert_eq!(task.actions[0].gas_limit, Some(400_000));
+
+    // Override re-creates the task
+    apps.dca_app.update_dca(
+        None,
+        DCAId(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(700_000),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    assert_eq!(apps.dca_app.dca(DCAId(1))?.gas_limit, Some(700_000));
+    let updated_task = apps
+        .cron_cat_app
+        .task_info(apps.dca_app.addr_str()?, DCAId(1).into())?
+        .task
+        .unwrap();
+    assert_eq!(updated_task.actions[0].gas_limit, Some(700_000));
+    assert_ne!(task.task_hash, updated_task.task_hash);
+
+    Ok(())
+}
+
+#[test]
+fn dynamic_refill() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, croncat_addrs) = setup()?;
+    let agent = mock.addr_make(AGENT);
+    let refill_attr = |res: &AppResponse| {
+        res.events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .find(|attr| attr.key == "refill")
+            .map(|attr| attr.value.clone())
+    };
+
+    // Refill on every convert, for 10 executions but at most 1 token
+    apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Uint128::one()),
+        None,
+        None,
+        None,
+        Some(10),
+        Some(Uint128::new(1_000_000_000)),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+
+    // Consumption is not known yet, refilled by the creation amount
+    mock.wait_blocks(1)?;
+    let res = mock.app.borrow_mut().execute_contract(
+        agent.clone(),
+        croncat_addrs.manager.clone(),
+        &ManagerExecuteMsg::ProxyCall(None),
+        &[],
+    )?;
+    assert_eq!(refill_attr(&res), Some("5000000".to_owned()));
+
+    // Sized by the observed consumption, capped by the max refill
+    mock.wait_blocks(1)?;
+    let res = mock.app.borrow_mut().execute_contract(
+        agent.clone(),
+        croncat_addrs.manager.clone(),
+        &ManagerExecuteMsg::ProxyCall(None),
+        &[],
+    )?;
+    assert_eq!(refill_attr(&res), Some("1".to_owned()));
+
+    // Without the cap the refill covers the 10 executions
+    apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Uint128::new(1_000_000_000)),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    mock.wait_blocks(1)?;
+    let res = mock.app.borrow_mut().execute_contract(
+        agent,
+        croncat_addrs.manager.clone(),
+        &ManagerExecuteMsg::ProxyCall(None),
+        &[],
+    )?;
+    let status: FundingStatusResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::FundingStatus {
+                dca_id: DCAId(1),
+            }))?;
+    let per_execution = status.task_cost_per_execution.unwrap();
+    assert_eq!(
+        refill_attr(&res),
+        Some((per_execution * Uint128::new(10)).to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn funding_status() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _croncat_addrs) = setup()?;
+
+    apps.dca_app.update_config(
+        None,
+        None,
+        None,
+        None,
+        Some(Uint128::new(1_000_000)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        None,
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 1_000_u128),
+        USD.into(),
+    )?;
+    let native_balance = mock.query_balance(&account.proxy()?, DENOM)?;
+
+    // Task cost is not observed yet, source funds are the limit
+    let status: FundingStatusResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::FundingStatus {
+                dca_id: DCAId(1),
+            }))?;
+    assert_eq!(
+        status,
+        FundingStatusResponse {
+            dca_id: DCAId(1),
+            task_balance: Some(Uint128::new(5_000_000)),
+            task_cost_per_execution: None,
+            native_balance,
+            source_balance: Uint128::new(10_000),
+            gas_runway: None,
+            source_runway: 100,
+            remaining_executions: 100,
+        }
+    );
+
+    // Keeper tips are paid from the native balance
+    let statuses: ListFundingStatusResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::ListFundingStatus {
+                start_after: None,
+                limit: None,
+            }))?;
+    assert_eq!(statuses.statuses.len(), 2);
+    let keeper_status = &statuses.statuses[1];
+    assert_eq!(keeper_status.task_balance, None);
+    assert_eq!(
+        keeper_status.gas_runway,
+        Some((native_balance.u128() / 1_000_000) as u64)
+    );
+    assert_eq!(keeper_status.source_runway, 10);
+    assert_eq!(keeper_status.remaining_executions, 10);
+
+    // Paused DCA won't execute
+    apps.dca_app.pause_dca(DCAId(1))?;
+    let status: FundingStatusResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::FundingStatus {
+                dca_id: DCAId(1),
+            }))?;
+    assert_eq!(status.task_balance, None);
+    assert_eq!(status.remaining_executions, 0);
+
+    // Budget and execution limits end the DCA before the funds run out
+    apps.dca_app.create_dca(
+        None,
+        Some(Uint128::new(250)),
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        None,
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    apps.dca_app.create_dca(
+        None,
+        None,
+        WYNDEX.to_owned(),
+        None,
+        Frequency::EveryNBlocks(1),
+        None,
+        Some(2),
+        None,
+        None,
+        Some(Schedule::Keeper),
+        AnsAsset::new(EUR, 100_u128),
+        USD.into(),
+    )?;
+    let statuses: ListFundingStatusResponse =
+        apps.dca_app
+            .query(&QueryMsg::from(DCAQueryMsg::ListFundingStatus {
+                start_after: Some(DCAId(2)),
+                limit: None,
+            }))?;
+    let runways: Vec<(u64, u64)> = statuses
+        .statuses
+        .iter()
+        .map(|status| (status.source_runway, status.remaining_executions))
+        .collect();
+    // Last purchase spends the remaining 50 of the budget
+    assert_eq!(runways, vec![(3, 3), (2, 2)]);
+
+    // Active DCAs share the account balances
+    let totals: FundingTotalsResponse = apps
+        .dca_app
+        .query(&QueryMsg::from(DCAQueryMsg::FundingTotals {}))?;
+    let native_balance = mock.query_balance(&account.proxy()?, DENOM)?;
+    assert_eq!(
+        totals,
+        FundingTotalsResponse {
+            native_balance,
+            task_balance: Uint128::zero(),
+            gas_demand: Uint128::new(3_000_000),
+            gas_runway: Some((native_balance.u128() / 3_000_000) as u64),
+            sources: vec![SourceFunding {
+                asset: AssetEntry::new(EUR),
+                balance: Uint128::new(10_000),
+                demand: Uint128::new(1_200),
+                runway: 8,
+            }],
         }
     );
 